@@ -0,0 +1,271 @@
+use std::path::PathBuf;
+
+use clap::{Parser, CommandFactory, ErrorKind};
+
+use crate::controller::{config::GameConfig, input::KeyBindings, keymap::{Action, KeyMap, Player}, options::{GameMode, Difficulty, MatchOptions, PaddleControl}, replay::Replay};
+
+/*
+    Command line.
+        Clap handles the flags that simply can't be used together; the combinations that depend on
+        values (game modes, the size of the arena, what a replay was recorded with) are checked in
+        `into_session`, which also loads the config and replay files so that every mistake is
+        reported before a window opens.
+ */
+#[derive(Debug, Parser)]
+#[clap(name = "rust-pong", version, about = "Pong, played against the computer, a friend, or nobody at all")]
+pub struct Cli {
+    /// Width of the play area in pixels [default: 1280, or the replay's]
+    #[clap(long, value_name = "PIXELS")]
+    width: Option<usize>,
+
+    /// Height of the play area in pixels [default: 600, or the replay's]
+    #[clap(long, value_name = "PIXELS")]
+    height: Option<usize>,
+
+    /// Cover the whole screen, scaling the play area to fit
+    #[clap(long, conflicts_with = "headless")]
+    fullscreen: bool,
+
+    /// Who plays: solo (1p, you against the computer), versus (2p, two players on one keyboard) or demo (cpu, computer against computer)
+    #[clap(short, long, value_name = "MODE")]
+    mode: Option<GameMode>,
+
+    /// What the left paddle is steered with: keyboard (keys or a game controller) or mouse [default: keyboard]
+    #[clap(long, value_name = "CONTROL", conflicts_with = "headless")]
+    control: Option<PaddleControl>,
+
+    /// TOML file of key bindings, read if it exists and written when keys are rebound in game
+    #[clap(short, long, value_name = "FILE")]
+    keymap: Option<PathBuf>,
+
+    /// Keys for the left paddle: ws, ik or arrows, overriding the key map [default: ws]
+    #[clap(long, value_name = "KEYS")]
+    left_keys: Option<KeyBindings>,
+
+    /// Keys for the right paddle in versus mode: ws, ik or arrows, overriding the key map [default: arrows]
+    #[clap(long, value_name = "KEYS")]
+    right_keys: Option<KeyBindings>,
+
+    /// How well the computer plays: easy, normal or hard [default: normal]
+    #[clap(short, long, value_name = "LEVEL")]
+    difficulty: Option<Difficulty>,
+
+    /// Points needed to win, overriding the config file
+    #[clap(long, value_name = "POINTS")]
+    win_score: Option<u8>,
+
+    /// Seed for the random serves, making a match repeatable
+    #[clap(long, conflicts_with = "playback")]
+    seed: Option<u64>,
+
+    /// TOML file of match rules
+    #[clap(short, long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Simulate the match without a window and print the result
+    #[clap(long)]
+    headless: bool,
+
+    /// Give up on a headless match after this many ticks
+    #[clap(long, requires = "headless")]
+    ticks: Option<u64>,
+
+    /// Save your inputs in the last match played to FILE once the game ends
+    #[clap(long, value_name = "FILE", conflicts_with_all = &["playback", "headless"])]
+    record: Option<PathBuf>,
+
+    /// Play back the inputs saved in FILE instead of reading the keyboard
+    #[clap(long, value_name = "FILE")]
+    playback: Option<PathBuf>,
+}
+
+// everything main needs to start a match
+pub struct Session {
+    pub width: usize,
+    pub height: usize,
+    pub config: GameConfig,
+    pub options: MatchOptions,
+    // the tick budget, when running without a window
+    pub headless: Option<u64>
+}
+
+impl Cli {
+    pub const DEFAULT_WIDTH: usize = 1280;
+    pub const DEFAULT_HEIGHT: usize = 600;
+    // a little over two hours at the default tick rate
+    pub const DEFAULT_TICK_BUDGET: u64 = 1_000_000;
+
+    fn error(kind: ErrorKind, message: impl std::fmt::Display) -> clap::Error {
+        Self::command().error(kind, message)
+    }
+
+    pub fn into_session(self) -> Result<Session, clap::Error> {
+        let mut config = match &self.config {
+            Some(path) => GameConfig::from_file(path)
+                .map_err(|e| Self::error(ErrorKind::Io, format!("{}: {e}", path.display())))?,
+            None => GameConfig::default()
+        };
+        if let Some(win_score) = self.win_score {
+            if win_score == 0 {
+                return Err(Self::error(ErrorKind::InvalidValue, "--win-score must be at least 1"))
+            }
+            config.win_score = win_score;
+        }
+
+        let playback = match &self.playback {
+            Some(path) => Some(Replay::load(path)
+                .map_err(|e| Self::error(ErrorKind::Io, format!("{}: {e}", path.display())))?),
+            None => None
+        };
+
+        // a replay only drives the left paddle, so it implies a solo match
+        let mode = match (self.mode, &playback, self.headless) {
+            (Some(mode), _, _) => mode,
+            (None, Some(_), _) => GameMode::SOLO,
+            (None, None, true) => GameMode::DEMO,
+            (None, None, false) => GameMode::SOLO
+        };
+        match mode {
+            GameMode::VERSUS if self.difficulty.is_some() => {
+                return Err(Self::error(ErrorKind::ArgumentConflict, "--difficulty has no computer to apply to in --mode versus"))
+            },
+            GameMode::VERSUS if self.headless => {
+                return Err(Self::error(ErrorKind::ArgumentConflict, "--headless has nobody at the keyboard; use --mode demo or --playback"))
+            },
+            GameMode::VERSUS if self.record.is_some() || playback.is_some() => {
+                return Err(Self::error(ErrorKind::ArgumentConflict, "replays only hold one player's inputs; they cannot be used with --mode versus"))
+            },
+            GameMode::DEMO | GameMode::SOLO if self.right_keys.is_some() => {
+                return Err(Self::error(ErrorKind::ArgumentConflict, "--right-keys is only used in --mode versus"))
+            },
+            GameMode::DEMO if self.left_keys.is_some() => {
+                return Err(Self::error(ErrorKind::ArgumentConflict, "--left-keys has no player to apply to in --mode demo"))
+            },
+            GameMode::DEMO if self.control.is_some() => {
+                return Err(Self::error(ErrorKind::ArgumentConflict, "--control has no player to apply to in --mode demo"))
+            },
+            _ if self.control.is_some() && playback.is_some() => {
+                return Err(Self::error(ErrorKind::ArgumentConflict, "--playback drives the left paddle itself; it cannot be used with --control"))
+            },
+            GameMode::DEMO if playback.is_some() => {
+                return Err(Self::error(ErrorKind::ArgumentConflict, "--playback needs a human paddle to drive; it cannot be used with --mode demo"))
+            },
+            GameMode::DEMO if self.record.is_some() => {
+                return Err(Self::error(ErrorKind::ArgumentConflict, "there is nobody to record in --mode demo"))
+            },
+            GameMode::SOLO if self.headless && playback.is_none() => {
+                return Err(Self::error(ErrorKind::ArgumentConflict, "--headless has nobody at the keyboard; use --mode demo or --playback"))
+            },
+            _ => ()
+        }
+
+        if let (Some(left_keys), Some(right_keys)) = (self.left_keys, self.right_keys) {
+            if left_keys == right_keys {
+                return Err(Self::error(ErrorKind::ArgumentConflict, format!("both players cannot use the {left_keys} keys")))
+            }
+        }
+        let mut keymap = match &self.keymap {
+            Some(path) if path.exists() => KeyMap::from_file(path)
+                .map_err(|e| Self::error(ErrorKind::Io, format!("{}: {e}", path.display())))?,
+            _ => KeyMap::default()
+        };
+        for (player, keys) in [(Player::LEFT, self.left_keys), (Player::RIGHT, self.right_keys)] {
+            if let Some(keys) = keys {
+                keymap.set_paddle_keys(player, keys);
+            }
+        }
+        let humans: &[Player] = match mode {
+            GameMode::SOLO => &[Player::LEFT],
+            GameMode::VERSUS => &Player::ALL,
+            GameMode::DEMO => &[]
+        };
+        for player in humans {
+            if keymap.keys_for(*player, Action::MOVE_UP).is_empty() || keymap.keys_for(*player, Action::MOVE_DOWN).is_empty() {
+                return Err(Self::error(ErrorKind::ArgumentConflict, format!("the {player} has no keys to move with")))
+            }
+        }
+
+        let (default_width, default_height) = playback.as_ref()
+            .map(Replay::get_area)
+            .unwrap_or((Self::DEFAULT_WIDTH, Self::DEFAULT_HEIGHT));
+        let (width, height) = (self.width.unwrap_or(default_width), self.height.unwrap_or(default_height));
+        if let Some(replay) = &playback {
+            if replay.get_area() != (width, height) {
+                let (w, h) = replay.get_area();
+                return Err(Self::error(ErrorKind::ArgumentConflict, format!("the replay was recorded in a {w}x{h} play area, not {width}x{height}")))
+            }
+        }
+        config.validate_for(width, height).map_err(|e| Self::error(ErrorKind::InvalidValue, e))?;
+
+        Ok(Session {
+            width,
+            height,
+            config,
+            options: MatchOptions {
+                mode,
+                difficulty: self.difficulty.unwrap_or(Difficulty::NORMAL),
+                control: self.control.unwrap_or(PaddleControl::KEYBOARD),
+                keymap,
+                keymap_path: self.keymap,
+                fullscreen: self.fullscreen,
+                seed: self.seed,
+                record: self.record,
+                playback
+            },
+            headless: if self.headless {Some(self.ticks.unwrap_or(Self::DEFAULT_TICK_BUDGET))} else {None}
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{Parser, ErrorKind};
+
+    use crate::controller::{keymap::{Action, Key, Player}, options::{GameMode, Difficulty}};
+
+    use super::Cli;
+
+    fn session(args: &[&str]) -> Result<super::Session, clap::Error> {
+        Cli::try_parse_from(std::iter::once("rust-pong").chain(args.iter().copied()))?.into_session()
+    }
+
+    #[test]
+    fn defaults_to_a_solo_window() {
+        let session = session(&[]).unwrap();
+        assert_eq!((session.width, session.height), (Cli::DEFAULT_WIDTH, Cli::DEFAULT_HEIGHT));
+        assert_eq!(session.options.mode, GameMode::SOLO);
+        assert_eq!(session.options.difficulty, Difficulty::NORMAL);
+        assert!(session.headless.is_none());
+    }
+
+    #[test]
+    fn parses_mode_aliases_and_overrides() {
+        let session = session(&["--mode", "cpu", "-d", "hard", "--headless", "--ticks", "50", "--win-score", "5"]).unwrap();
+        assert_eq!(session.options.mode, GameMode::DEMO);
+        assert_eq!(session.options.difficulty, Difficulty::HARD);
+        assert_eq!(session.headless, Some(50));
+        assert_eq!(session.config.win_score, 5);
+    }
+
+    #[test]
+    fn rejects_invalid_combinations() {
+        let kind = |args: &[&str]| session(args).err().map(|e| e.kind());
+        assert_eq!(kind(&["--headless", "--fullscreen"]), Some(ErrorKind::ArgumentConflict));
+        assert_eq!(kind(&["--ticks", "10"]), Some(ErrorKind::MissingRequiredArgument));
+        assert_eq!(kind(&["--headless", "--mode", "solo"]), Some(ErrorKind::ArgumentConflict));
+        assert_eq!(kind(&["--width", "20"]), Some(ErrorKind::InvalidValue));
+        assert!(matches!(kind(&["--mode", "3p"]), Some(ErrorKind::ValueValidation)));
+        assert_eq!(kind(&["--mode", "2p", "--difficulty", "easy"]), Some(ErrorKind::ArgumentConflict));
+        assert_eq!(kind(&["--mode", "2p", "--left-keys", "arrows"]), Some(ErrorKind::ArgumentConflict));
+        assert_eq!(kind(&["--right-keys", "ik"]), Some(ErrorKind::ArgumentConflict));
+        assert_eq!(kind(&["--mode", "demo", "--control", "mouse"]), Some(ErrorKind::ArgumentConflict));
+    }
+
+    #[test]
+    fn versus_takes_independent_bindings() {
+        let session = session(&["--mode", "versus", "--left-keys", "ik", "--right-keys", "ws"]).unwrap();
+        assert_eq!(session.options.mode, GameMode::VERSUS);
+        assert_eq!(session.options.keymap.keys_for(Player::LEFT, Action::MOVE_UP), &[Key(105)]);
+        assert_eq!(session.options.keymap.keys_for(Player::RIGHT, Action::MOVE_DOWN), &[Key(115)]);
+    }
+}
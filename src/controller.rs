@@ -1 +1,10 @@
-pub mod pong_controller;
\ No newline at end of file
+pub mod pong_controller;
+pub mod clock;
+pub mod config;
+pub mod input;
+pub mod keymap;
+pub mod opponent;
+pub mod paddle_controller;
+pub mod options;
+pub mod replay;
+pub mod state;
\ No newline at end of file
@@ -0,0 +1,118 @@
+use std::time::{Duration, Instant};
+
+/*
+    Fixed-timestep game clock.
+        Real time is accumulated between calls to `advance`, and the simulation is stepped in
+        whole ticks of `tick_length` drained from that accumulator. Whatever is left over is a
+        fraction of a tick, which the renderer uses to interpolate between an object's last
+        position and its current one.
+ */
+pub struct GameClock {
+    tick_length: Duration,
+    accumulator: Duration,
+    last_instant: Instant,
+    ticks: u64
+}
+
+impl GameClock {
+    pub const DEFAULT_TICK_RATE: f64 = 120.0;
+    // upper bound on the ticks a single `advance` may request, so a stalled frame can't snowball
+    const MAX_TICKS_PER_ADVANCE: u32 = 10;
+
+    pub fn new(tick_rate: f64) -> Self {
+        assert!(tick_rate > 0.0, "Tick rate must be positive!");
+        Self {
+            tick_length: Duration::from_secs_f64(1.0 / tick_rate),
+            accumulator: Duration::ZERO,
+            last_instant: Instant::now(),
+            ticks: 0
+        }
+    }
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TICK_RATE)
+    }
+}
+
+impl GameClock {
+    pub fn get_tick_length(&self) -> Duration {
+        self.tick_length
+    }
+
+    pub fn get_tick_rate(&self) -> f64 {
+        1.0 / self.tick_length.as_secs_f64()
+    }
+
+    pub fn get_ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    // restarts the real-time reference point without touching the tick count
+    pub fn restart(&mut self) {
+        self.last_instant = Instant::now();
+        self.accumulator = Duration::ZERO;
+    }
+
+    // accumulates the real time elapsed since the previous call and returns how many ticks are due
+    pub fn advance(&mut self) -> u32 {
+        let now = Instant::now();
+        let elapsed = now - self.last_instant;
+        self.last_instant = now;
+        self.accumulate(elapsed)
+    }
+
+    pub fn accumulate(&mut self, elapsed: Duration) -> u32 {
+        self.accumulator += elapsed;
+        let mut due = 0;
+        while self.accumulator >= self.tick_length {
+            self.accumulator -= self.tick_length;
+            due += 1;
+        }
+        if due > Self::MAX_TICKS_PER_ADVANCE {
+            // drop the backlog rather than trying to catch up on it
+            due = Self::MAX_TICKS_PER_ADVANCE;
+        }
+        self.ticks += due as u64;
+        due
+    }
+
+    // fraction of a tick sitting in the accumulator, in [0, 1)
+    pub fn alpha(&self) -> f64 {
+        self.accumulator.as_secs_f64() / self.tick_length.as_secs_f64()
+    }
+
+    pub fn time_until_next_tick(&self) -> Duration {
+        self.tick_length.saturating_sub(self.accumulator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::GameClock;
+
+    #[test]
+    fn accumulate_drains_whole_ticks() {
+        let mut clock = GameClock::new(100.0);
+        assert_eq!(clock.accumulate(Duration::from_millis(25)), 2);
+        assert!((clock.alpha() - 0.5).abs() < 1e-9);
+        assert_eq!(clock.accumulate(Duration::from_millis(5)), 1);
+        assert!(clock.alpha() < 1e-9);
+        assert_eq!(clock.get_ticks(), 3);
+    }
+
+    #[test]
+    fn accumulate_caps_backlog() {
+        let mut clock = GameClock::new(100.0);
+        assert_eq!(clock.accumulate(Duration::from_secs(10)), GameClock::MAX_TICKS_PER_ADVANCE);
+    }
+
+    #[test]
+    fn tick_rate_round_trips() {
+        let clock = GameClock::new(120.0);
+        assert!((clock.get_tick_rate() - 120.0).abs() < 1e-3);
+    }
+}
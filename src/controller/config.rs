@@ -0,0 +1,252 @@
+use std::{fmt::Display, fs, io, path::Path};
+
+use serde::Deserialize;
+
+use crate::{controller::{clock::GameClock, pong_controller::Controller}, model::pong::{PlayArea, game_objects::behaviors::English}, view::assets::style::Shape};
+
+/*
+    Match rules.
+        Everything a designer might want to tune without recompiling. Any key missing from a config
+        file keeps its default, so a file only needs to mention what it changes, e.g.
+
+            win_score = 5
+            max_speed = 8.0
+            paddle_height = 80
+
+        Distances are in pixels and speeds in pixels per tick.
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    pub tick_rate: f64,
+    pub win_score: u8,
+    pub serve_speed: f64,
+    pub max_speed: f64,
+    pub speed_increment: f64,
+    pub momentum_upper: f64,
+    pub momentum_lower: f64,
+    pub momentum_increment: f64,
+    pub momentum_decay: f64,
+    pub wall_thickness: usize,
+    pub ball_width: usize,
+    pub paddle_width: usize,
+    pub paddle_height: usize,
+    pub paddle_gap: usize,
+    pub goal_width: usize,
+    pub paddle_max_deflection: f64,
+    pub paddle_spin: f64,
+    pub max_balls: usize,
+    pub multiball_hits: u32,
+    pub serve_countdown: f64,
+    pub serve_cone: f64,
+    pub serve_to: ServeRule,
+    pub ball_shape: Shape,
+    pub paddle_shape: Shape,
+    pub wall_shape: Shape,
+    pub goal_shape: Shape
+}
+
+// who the ball is served towards after a point; the first serve of a match goes either way
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServeRule {
+    // the player who just lost the point
+    LOSER,
+    // the other player from last time
+    ALTERNATE
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            tick_rate: GameClock::DEFAULT_TICK_RATE,
+            win_score: Controller::WIN_SCORE,
+            serve_speed: Controller::SERVE_SPEED,
+            max_speed: PlayArea::MAX_SPEED,
+            speed_increment: PlayArea::SPEED_INCREMENT,
+            momentum_upper: Controller::MOMENTUM_UPPER,
+            momentum_lower: Controller::MOMENTUM_LOWER,
+            momentum_increment: Controller::INCREMENT,
+            momentum_decay: Controller::DECAY,
+            wall_thickness: 10,
+            ball_width: 20,
+            paddle_width: 10,
+            paddle_height: 50,
+            paddle_gap: 30,
+            goal_width: 30,
+            paddle_max_deflection: English::DEFAULT_MAX_DEFLECTION,
+            paddle_spin: 4.0,
+            max_balls: 1,
+            multiball_hits: 0,
+            serve_countdown: 0.0,
+            serve_cone: 30.0,
+            serve_to: ServeRule::LOSER,
+            ball_shape: Shape::CIRCLE,
+            paddle_shape: Shape::RECTANGLE,
+            wall_shape: Shape::RECTANGLE,
+            goal_shape: Shape::RECTANGLE
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Invalid(String)
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(e)          => write!(f, "could not read config file: {e}"),
+            ConfigError::Parse(e)       => write!(f, "could not parse config file: {e}"),
+            ConfigError::Invalid(why)   => write!(f, "invalid config: {why}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<io::Error> for ConfigError {
+    fn from(e: io::Error) -> Self {
+        ConfigError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(e: toml::de::Error) -> Self {
+        ConfigError::Parse(e)
+    }
+}
+
+impl GameConfig {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    pub fn from_toml(source: &str) -> Result<Self, ConfigError> {
+        let config: GameConfig = toml::from_str(source)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    // rejects rules the game can't be played with, rather than failing somewhere mid-match
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |why: &str| Err(ConfigError::Invalid(why.to_string()));
+        if self.tick_rate <= 0.0 {
+            return invalid("tick_rate must be positive")
+        }
+        if self.win_score == 0 {
+            return invalid("win_score must be at least 1")
+        }
+        if self.max_speed <= 0.0 || self.serve_speed <= 0.0 {
+            return invalid("max_speed and serve_speed must be positive")
+        }
+        if self.momentum_lower >= 0.0 || self.momentum_upper <= 0.0 {
+            return invalid("momentum_lower must be negative and momentum_upper positive")
+        }
+        if self.momentum_increment <= 0.0 || self.momentum_decay < 0.0 {
+            return invalid("momentum_increment must be positive and momentum_decay non-negative")
+        }
+        if self.ball_width == 0 || self.paddle_width == 0 || self.paddle_height == 0 {
+            return invalid("ball and paddle dimensions must be non-zero")
+        }
+        if self.max_balls == 0 {
+            return invalid("max_balls must be at least 1")
+        }
+        if self.serve_countdown < 0.0 {
+            return invalid("serve_countdown can't be negative")
+        }
+        if !(0.0..90.0).contains(&self.serve_cone) {
+            return invalid("serve_cone must be at least 0 and under 90 degrees")
+        }
+        Ok(())
+    }
+
+    // as validate, and also that the walls, goals, paddles and ball all fit in a width by height play area
+    pub fn validate_for(&self, width: usize, height: usize) -> Result<(), ConfigError> {
+        self.validate()?;
+        let (min_width, min_height) = self.min_area()?;
+        if width < min_width || height < min_height {
+            return Err(ConfigError::Invalid(format!("the play area must be at least {min_width}x{min_height} with these rules, not {width}x{height}")))
+        }
+        Ok(())
+    }
+
+    // smallest play area that still fits the walls, both goals, both paddles and the ball
+    pub fn min_area(&self) -> Result<(usize, usize), ConfigError> {
+        let too_large = || ConfigError::Invalid("wall_thickness, paddle_gap, goal_width and the paddle and ball sizes are too large for any play area".to_string());
+        let width = self.wall_thickness.checked_add(self.paddle_gap.max(self.goal_width))
+            .and_then(|side| side.checked_add(self.paddle_width))
+            .and_then(|side| side.checked_mul(2))
+            .and_then(|sides| sides.checked_add(self.ball_width))
+            .ok_or_else(too_large)?;
+        let height = self.wall_thickness.checked_add(self.paddle_gap)
+            .and_then(|side| side.checked_mul(2))
+            .and_then(|sides| sides.checked_add(self.paddle_height.max(self.ball_width)))
+            .ok_or_else(too_large)?;
+        Ok((width, height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::view::assets::style::Shape;
+
+    use super::{GameConfig, ConfigError, ServeRule};
+
+    #[test]
+    fn empty_file_is_default() {
+        assert_eq!(GameConfig::from_toml("").unwrap(), GameConfig::default());
+    }
+
+    #[test]
+    fn partial_file_overrides_only_what_it_names() {
+        let config = GameConfig::from_toml("win_score = 7\npaddle_height = 80\nmax_speed = 9.5").unwrap();
+        assert_eq!(config.win_score, 7);
+        assert_eq!(config.paddle_height, 80);
+        assert_eq!(config.max_speed, 9.5);
+        assert_eq!(config.ball_width, GameConfig::default().ball_width);
+    }
+
+    #[test]
+    fn serve_rules_are_read_by_name() {
+        let config = GameConfig::from_toml("serve_to = \"alternate\"\nserve_cone = 10.0").unwrap();
+        assert_eq!(config.serve_to, ServeRule::ALTERNATE);
+        assert!(matches!(GameConfig::from_toml("serve_to = \"winner\""), Err(ConfigError::Parse(_))));
+        assert!(matches!(GameConfig::from_toml("serve_cone = 90.0"), Err(ConfigError::Invalid(_))));
+    }
+
+    #[test]
+    fn shapes_are_read_by_name() {
+        let config = GameConfig::from_toml("ball_shape = \"outlined_circle\"\nwall_shape = \"outlined_rectangle\"").unwrap();
+        assert_eq!(config.ball_shape, Shape::OUTLINED_CIRCLE);
+        assert_eq!(config.wall_shape, Shape::OUTLINED_RECTANGLE);
+        assert_eq!(config.paddle_shape, Shape::RECTANGLE);
+        assert!(matches!(GameConfig::from_toml("ball_shape = \"hexagon\""), Err(ConfigError::Parse(_))));
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!(matches!(GameConfig::from_toml("win_scroe = 7"), Err(ConfigError::Parse(_))));
+    }
+
+    #[test]
+    fn unplayable_rules_are_rejected() {
+        assert!(matches!(GameConfig::from_toml("win_score = 0"), Err(ConfigError::Invalid(_))));
+        assert!(matches!(GameConfig::from_toml("tick_rate = -1.0"), Err(ConfigError::Invalid(_))));
+        assert!(matches!(GameConfig::from_toml("max_balls = 0"), Err(ConfigError::Invalid(_))));
+    }
+
+    #[test]
+    fn the_arena_must_fit_the_play_area() {
+        let config = GameConfig::default();
+        let (width, height) = config.min_area().unwrap();
+        assert!(config.validate_for(width, height).is_ok());
+        assert!(matches!(config.validate_for(width - 1, height), Err(ConfigError::Invalid(_))));
+        let huge = GameConfig::from_toml(&format!("wall_thickness = {}", i64::MAX)).unwrap();
+        assert!(matches!(huge.min_area(), Err(ConfigError::Invalid(_))));
+        assert!(matches!(huge.validate_for(usize::MAX, usize::MAX), Err(ConfigError::Invalid(_))));
+    }
+}
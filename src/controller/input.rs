@@ -0,0 +1,316 @@
+use std::collections::{HashMap, HashSet, hash_map::Entry};
+
+use crate::view::input::InputEvent;
+
+// what a paddle is being asked to do for one tick, whoever is doing the asking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaddleInput {
+    UP,
+    DOWN,
+    IDLE,
+    // an analog stick's tilt, in percent from -100 (all the way up) to 100 (all the way down)
+    STICK(i8)
+}
+
+impl PaddleInput {
+    // as written in replay files
+    pub fn symbol(&self) -> String {
+        match self {
+            PaddleInput::UP         => String::from("U"),
+            PaddleInput::DOWN       => String::from("D"),
+            PaddleInput::IDLE       => String::from("."),
+            PaddleInput::STICK(t)   => format!("S{t}:"),
+        }
+    }
+
+    // splits a replay token such as "U14" or "S-40:3" into its input and run length
+    pub fn parse_token(token: &str) -> Option<(Self, usize)> {
+        let (input, run) = match token.strip_prefix('S') {
+            Some(stick) => {
+                let (tilt, run) = stick.split_once(':')?;
+                (PaddleInput::STICK(tilt.parse().ok().filter(|t: &i8| (-100..=100).contains(t))?), run)
+            },
+            None => {
+                let input = match token.chars().next()? {
+                    'U' => PaddleInput::UP,
+                    'D' => PaddleInput::DOWN,
+                    '.' => PaddleInput::IDLE,
+                    _   => return None
+                };
+                (input, &token[1..])
+            }
+        };
+        Some((input, run.parse().ok()?))
+    }
+}
+
+/*
+    Game controller state.
+        Buttons are tracked like keys, and the left stick's vertical position is kept as a tilt in
+        [-1, 1] with a dead zone around the middle so a stick at rest doesn't creep.
+ */
+#[derive(Debug, Default)]
+pub struct PadState {
+    pressed: HashSet<u8>,
+    just_pressed: HashSet<u8>,
+    stick_y: i16
+}
+
+impl PadState {
+    // SDL_CONTROLLER_AXIS_LEFTY
+    pub const STICK_Y_AXIS: u8 = 1;
+    pub const DEAD_ZONE: i16 = 8000;
+
+    pub fn is_down(&self, button: u8) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    pub fn was_pressed(&self, button: u8) -> bool {
+        self.just_pressed.contains(&button)
+    }
+
+    // how far the stick is pushed down, or up if negative, rescaled to start at the edge of the dead zone
+    pub fn stick_tilt(&self) -> f64 {
+        let magnitude = (self.stick_y as f64).abs();
+        let dead_zone = Self::DEAD_ZONE as f64;
+        if magnitude <= dead_zone {
+            return 0.0
+        }
+        let tilt = ((magnitude - dead_zone) / (i16::MAX as f64 - dead_zone)).min(1.0);
+        tilt * (self.stick_y as f64).signum()
+    }
+}
+
+/*
+    Input state.
+        Built up from the events the render thread forwards. Keys that are held down stay in the
+        pressed set until they are released, so any number of them can be held at once; the keys
+        that went down or came up since the previous tick are kept separately for actions that
+        should happen once per press rather than every tick the key is held. Game controllers are
+        numbered in the order they were plugged in. The mouse pointer is remembered where it was
+        last seen.
+ */
+#[derive(Debug, Default)]
+pub struct InputState {
+    pressed: HashSet<i32>,
+    // in the order they went down, so that the first of several keys pressed together is known
+    just_pressed: Vec<i32>,
+    just_released: HashSet<i32>,
+    quit_requested: bool,
+    pads: HashMap<i32, PadState>,
+    pad_order: Vec<i32>,
+    pointer: Option<(i32, i32)>
+}
+
+impl InputState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // forgets the edges seen during the previous tick; held keys stay held
+    pub fn begin_tick(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+        for pad in self.pads.values_mut() {
+            pad.just_pressed.clear();
+        }
+    }
+
+    pub fn apply(&mut self, event: InputEvent) {
+        match event {
+            InputEvent::KEYDOWN(key) => {
+                if self.pressed.insert(key) {
+                    self.just_pressed.push(key);
+                }
+            },
+            InputEvent::KEYUP(key) => {
+                if self.pressed.remove(&key) {
+                    self.just_released.insert(key);
+                }
+            },
+            InputEvent::QUIT => self.quit_requested = true,
+            InputEvent::PADADDED(id) => {
+                if let Entry::Vacant(entry) = self.pads.entry(id) {
+                    entry.insert(PadState::default());
+                    self.pad_order.push(id);
+                }
+            },
+            InputEvent::PADREMOVED(id) => {
+                self.pads.remove(&id);
+                self.pad_order.retain(|other| *other != id);
+            },
+            InputEvent::PADBUTTONDOWN(id, button) => {
+                if let Some(pad) = self.pads.get_mut(&id) {
+                    if pad.pressed.insert(button) {
+                        pad.just_pressed.insert(button);
+                    }
+                }
+            },
+            InputEvent::PADBUTTONUP(id, button) => {
+                if let Some(pad) = self.pads.get_mut(&id) {
+                    pad.pressed.remove(&button);
+                }
+            },
+            InputEvent::PADAXIS(id, axis, value) => {
+                if let (Some(pad), PadState::STICK_Y_AXIS) = (self.pads.get_mut(&id), axis) {
+                    pad.stick_y = value;
+                }
+            },
+            InputEvent::MOUSEMOTION(x, y) => self.pointer = Some((x, y))
+        }
+    }
+
+    // last known position of the mouse pointer, if it has moved over the window at all
+    pub fn get_pointer(&self) -> Option<(i32, i32)> {
+        self.pointer
+    }
+
+    // the nth game controller still plugged in
+    pub fn pad(&self, n: usize) -> Option<&PadState> {
+        self.pad_order.get(n).and_then(|id| self.pads.get(id))
+    }
+
+    pub fn is_down(&self, key: i32) -> bool {
+        self.pressed.contains(&key)
+    }
+
+    pub fn was_pressed(&self, key: i32) -> bool {
+        self.just_pressed.contains(&key)
+    }
+
+    pub fn was_released(&self, key: i32) -> bool {
+        self.just_released.contains(&key)
+    }
+
+    // keys that went down since the previous tick, first pressed first
+    pub fn just_pressed(&self) -> impl Iterator<Item = i32> + '_ {
+        self.just_pressed.iter().copied()
+    }
+
+    pub fn quit_requested(&self) -> bool {
+        self.quit_requested
+    }
+}
+
+/*
+    Key bindings.
+        The pair of keys that moves one paddle. Players can pick a preset at startup so that two of
+        them can share a keyboard without fighting over the same keys.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBindings {
+    up: i32,
+    down: i32
+}
+
+impl KeyBindings {
+    pub const WS: KeyBindings = KeyBindings::new(119, 115);
+    pub const IK: KeyBindings = KeyBindings::new(105, 107);
+    // SDLK_UP and SDLK_DOWN
+    pub const ARROWS: KeyBindings = KeyBindings::new(0x4000_0052, 0x4000_0051);
+
+    pub const fn new(up: i32, down: i32) -> Self {
+        Self {up, down}
+    }
+
+    pub fn get_up(&self) -> i32 {
+        self.up
+    }
+
+    pub fn get_down(&self) -> i32 {
+        self.down
+    }
+}
+
+impl std::fmt::Display for KeyBindings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            KeyBindings::WS     => write!(f, "ws"),
+            KeyBindings::IK     => write!(f, "ik"),
+            KeyBindings::ARROWS => write!(f, "arrows"),
+            KeyBindings {up, down} => write!(f, "{up}/{down}"),
+        }
+    }
+}
+
+impl std::str::FromStr for KeyBindings {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ws"        => Ok(KeyBindings::WS),
+            "ik"        => Ok(KeyBindings::IK),
+            "arrows"    => Ok(KeyBindings::ARROWS),
+            _ => Err(format!("unknown key bindings '{s}', expected one of: ws, ik, arrows"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::view::input::InputEvent;
+
+    use super::{KeyBindings, InputState, PaddleInput};
+
+    fn state_with(events: &[InputEvent]) -> InputState {
+        let mut state = InputState::new();
+        for event in events {
+            state.apply(*event);
+        }
+        state
+    }
+
+    #[test]
+    fn bindings_parse_by_name() {
+        assert_eq!("Arrows".parse::<KeyBindings>(), Ok(KeyBindings::ARROWS));
+        assert!("wasd".parse::<KeyBindings>().is_err());
+    }
+
+    #[test]
+    fn releasing_one_key_keeps_the_others_held() {
+        let mut state = state_with(&[InputEvent::KEYDOWN(119), InputEvent::KEYDOWN(105)]);
+        state.begin_tick();
+        state.apply(InputEvent::KEYUP(105));
+        assert!(state.is_down(119) && !state.is_down(105));
+        assert!(state.was_released(105) && !state.was_pressed(119));
+    }
+
+    #[test]
+    fn presses_are_kept_in_order() {
+        let state = state_with(&[InputEvent::KEYDOWN(115), InputEvent::KEYDOWN(97), InputEvent::KEYDOWN(119)]);
+        assert_eq!(state.just_pressed().collect::<Vec<i32>>(), vec![115, 97, 119]);
+    }
+
+    #[test]
+    fn pads_are_numbered_by_connection() {
+        let mut state = state_with(&[InputEvent::PADADDED(9), InputEvent::PADADDED(4), InputEvent::PADAXIS(4, 1, i16::MAX)]);
+        assert_eq!(state.pad(1).unwrap().stick_tilt(), 1.0);
+        assert_eq!(state.pad(0).unwrap().stick_tilt(), 0.0);
+        state.apply(InputEvent::PADREMOVED(9));
+        assert_eq!(state.pad(0).unwrap().stick_tilt(), 1.0);
+        assert!(state.pad(1).is_none());
+    }
+
+    #[test]
+    fn stick_has_a_dead_zone() {
+        let state = state_with(&[InputEvent::PADADDED(0), InputEvent::PADAXIS(0, 1, -7000)]);
+        assert_eq!(state.pad(0).unwrap().stick_tilt(), 0.0);
+        let state = state_with(&[InputEvent::PADADDED(0), InputEvent::PADAXIS(0, 1, -20_384)]);
+        assert!((state.pad(0).unwrap().stick_tilt() + 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn replay_tokens_round_trip() {
+        for input in [PaddleInput::UP, PaddleInput::IDLE, PaddleInput::STICK(-40), PaddleInput::STICK(100)] {
+            assert_eq!(PaddleInput::parse_token(&format!("{}{}", input.symbol(), 12)), Some((input, 12)));
+        }
+        assert_eq!(PaddleInput::parse_token("S120:3"), None);
+    }
+
+    #[test]
+    fn edges_last_a_single_tick() {
+        let mut state = state_with(&[InputEvent::KEYDOWN(27)]);
+        assert!(state.was_pressed(27));
+        state.begin_tick();
+        assert!(!state.was_pressed(27) && state.is_down(27));
+    }
+}
@@ -0,0 +1,506 @@
+use std::{collections::BTreeMap, fmt::Display, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{controller::{config::ConfigError, input::{InputState, KeyBindings, PaddleInput}}, model::pong::Role};
+
+// what a key press means to the game, independent of which key it was
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Action {
+    MOVE_UP,
+    MOVE_DOWN,
+    PAUSE,
+    QUIT,
+    SERVE,
+    REBIND
+}
+
+impl Action {
+    pub const ALL: [Action; 6] = [Action::MOVE_UP, Action::MOVE_DOWN, Action::PAUSE, Action::QUIT, Action::SERVE, Action::REBIND];
+
+    // as written in key map files
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::MOVE_UP     => "move_up",
+            Action::MOVE_DOWN   => "move_down",
+            Action::PAUSE       => "pause",
+            Action::QUIT        => "quit",
+            Action::SERVE       => "serve",
+            Action::REBIND      => "rebind",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::MOVE_UP     => write!(f, "move up"),
+            Action::MOVE_DOWN   => write!(f, "move down"),
+            Action::PAUSE       => write!(f, "pause"),
+            Action::QUIT        => write!(f, "quit"),
+            Action::SERVE       => write!(f, "serve"),
+            Action::REBIND      => write!(f, "rebind keys"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Player {
+    LEFT,
+    RIGHT
+}
+
+impl Player {
+    pub const ALL: [Player; 2] = [Player::LEFT, Player::RIGHT];
+
+    pub fn opponent(&self) -> Player {
+        match self {
+            Player::LEFT => Player::RIGHT,
+            Player::RIGHT => Player::LEFT
+        }
+    }
+
+    pub fn paddle(&self) -> Role {
+        match self {
+            Player::LEFT => Role::LEFT_PADDLE,
+            Player::RIGHT => Role::RIGHT_PADDLE
+        }
+    }
+
+    // which game controller belongs to the player, by the order they were plugged in
+    pub fn pad_index(&self) -> usize {
+        match self {
+            Player::LEFT => 0,
+            Player::RIGHT => 1
+        }
+    }
+}
+
+impl Display for Player {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Player::LEFT    => write!(f, "left player"),
+            Player::RIGHT   => write!(f, "right player"),
+        }
+    }
+}
+
+/*
+    Keys.
+        An SDL keycode that reads and writes itself by name in key map files ("w", "up", "escape",
+        "f1", ...). Keys without a name are written as their raw keycode, which is read back as well.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Key(pub i32);
+
+impl Key {
+    pub const ESCAPE: Key = Key(27);
+
+    // SDLK_* values for the keys that don't stand for a printable character
+    const NAMED: [(&'static str, i32); 23] = [
+        ("backspace", 8), ("tab", 9), ("return", 13), ("escape", 27), ("space", 32), ("delete", 127),
+        ("right", 0x4000_004F), ("left", 0x4000_0050), ("down", 0x4000_0051), ("up", 0x4000_0052),
+        ("f1", 0x4000_003A), ("f2", 0x4000_003B), ("f3", 0x4000_003C), ("f4", 0x4000_003D),
+        ("f5", 0x4000_003E), ("f6", 0x4000_003F), ("f7", 0x4000_0040), ("f8", 0x4000_0041),
+        ("f9", 0x4000_0042), ("f10", 0x4000_0043), ("f11", 0x4000_0044), ("f12", 0x4000_0045),
+        ("kp_enter", 0x4000_0058)
+    ];
+
+    pub fn code(&self) -> i32 {
+        self.0
+    }
+
+    pub fn name(&self) -> String {
+        if let Some((name, _)) = Self::NAMED.iter().find(|(_, code)| *code == self.0) {
+            return name.to_string()
+        }
+        match u8::try_from(self.0) {
+            Ok(c) if c.is_ascii_graphic() => (c as char).to_string(),
+            _ => self.0.to_string()
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        if let Some((_, code)) = Self::NAMED.iter().find(|(known, _)| *known == name) {
+            return Some(Key(*code))
+        }
+        let mut chars = name.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_graphic() => Some(Key(c as i32)),
+            _ => name.parse().ok().map(Key)
+        }
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl TryFrom<String> for Key {
+    type Error = String;
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        Self::from_name(&name).ok_or_else(|| format!("unknown key '{name}'"))
+    }
+}
+
+impl From<Key> for String {
+    fn from(key: Key) -> Self {
+        key.name()
+    }
+}
+
+type Bindings = BTreeMap<Action, Vec<Key>>;
+
+/*
+    Key map.
+        Maps keys to actions, separately for each player. Any player's PAUSE, QUIT or REBIND key
+        works for everyone. A key is bound to at most one action of one player. Key map files are
+        TOML with a table per player, for example
+
+            [left]
+            move_up = ["w"]
+            move_down = ["s", "down"]
+
+            [right]
+            move_up = ["i"]
+
+        Actions left out of a file keep their default keys. Game controllers aren't rebindable:
+        the d-pad and left stick move, A serves, start pauses and back quits.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMap {
+    left: Bindings,
+    right: Bindings
+}
+
+// a key map file as written, actions by name
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct KeyMapFile {
+    left: BTreeMap<String, Vec<Key>>,
+    right: BTreeMap<String, Vec<Key>>
+}
+
+impl KeyMapFile {
+    fn named(bindings: &Bindings) -> BTreeMap<String, Vec<Key>> {
+        bindings.iter().map(|(action, keys)| (action.name().to_string(), keys.clone())).collect()
+    }
+
+    fn parsed(named: BTreeMap<String, Vec<Key>>) -> Result<Bindings, ConfigError> {
+        named.into_iter()
+            .map(|(name, keys)| match Action::from_name(&name) {
+                Some(action) => Ok((action, keys)),
+                None => Err(ConfigError::Invalid(format!("unknown action '{name}'")))
+            })
+            .collect()
+    }
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let mut keymap = Self {left: Bindings::new(), right: Bindings::new()};
+        keymap.set_paddle_keys(Player::LEFT, KeyBindings::WS);
+        keymap.set_paddle_keys(Player::RIGHT, KeyBindings::ARROWS);
+        keymap.bind(Player::LEFT, Action::PAUSE, Key::from_name("p").unwrap());
+        keymap.bind(Player::LEFT, Action::QUIT, Key::ESCAPE);
+        keymap.bind(Player::LEFT, Action::SERVE, Key::from_name("space").unwrap());
+        keymap.bind(Player::LEFT, Action::REBIND, Key::from_name("f1").unwrap());
+        keymap.bind(Player::RIGHT, Action::SERVE, Key::from_name("return").unwrap());
+        keymap
+    }
+}
+
+impl KeyMap {
+    fn bindings(&self, player: Player) -> &Bindings {
+        match player {
+            Player::LEFT => &self.left,
+            Player::RIGHT => &self.right
+        }
+    }
+
+    fn bindings_mut(&mut self, player: Player) -> &mut Bindings {
+        match player {
+            Player::LEFT => &mut self.left,
+            Player::RIGHT => &mut self.right
+        }
+    }
+
+    pub fn keys_for(&self, player: Player, action: Action) -> &[Key] {
+        self.bindings(player).get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    // the action a key is bound to, and whose it is
+    pub fn lookup(&self, key: Key) -> Option<(Player, Action)> {
+        Player::ALL.into_iter().find_map(|player| {
+            self.bindings(player).iter()
+                .find(|(_, keys)| keys.contains(&key))
+                .map(|(action, _)| (player, *action))
+        })
+    }
+
+    // makes key the only key for the action, taking it away from whatever it was bound to before
+    pub fn bind(&mut self, player: Player, action: Action, key: Key) {
+        self.unbind_key(key);
+        self.bindings_mut(player).insert(action, vec![key]);
+    }
+
+    fn unbind_key(&mut self, key: Key) {
+        for player in Player::ALL {
+            for keys in self.bindings_mut(player).values_mut() {
+                keys.retain(|bound| *bound != key);
+            }
+        }
+    }
+
+    pub fn set_paddle_keys(&mut self, player: Player, keys: KeyBindings) {
+        self.bind(player, Action::MOVE_UP, Key(keys.get_up()));
+        self.bind(player, Action::MOVE_DOWN, Key(keys.get_down()));
+    }
+}
+
+impl KeyMap {
+    // SDL_GameControllerButton values
+    const PAD_BUTTONS: [(Action, u8); 5] = [
+        (Action::MOVE_UP, 11), (Action::MOVE_DOWN, 12), (Action::SERVE, 0), (Action::PAUSE, 6), (Action::QUIT, 4)
+    ];
+
+    fn pad_button(action: Action) -> Option<u8> {
+        Self::PAD_BUTTONS.iter().find(|(bound, _)| *bound == action).map(|(_, button)| *button)
+    }
+
+    pub fn is_down(&self, player: Player, action: Action, state: &InputState) -> bool {
+        let on_pad = match (state.pad(player.pad_index()), Self::pad_button(action)) {
+            (Some(pad), Some(button)) => pad.is_down(button),
+            _ => false
+        };
+        on_pad || self.keys_for(player, action).iter().any(|key| state.is_down(key.code()))
+    }
+
+    pub fn was_pressed(&self, player: Player, action: Action, state: &InputState) -> bool {
+        let on_pad = match (state.pad(player.pad_index()), Self::pad_button(action)) {
+            (Some(pad), Some(button)) => pad.was_pressed(button),
+            _ => false
+        };
+        on_pad || self.keys_for(player, action).iter().any(|key| state.was_pressed(key.code()))
+    }
+
+    // for actions that belong to nobody in particular
+    pub fn anyone_pressed(&self, action: Action, state: &InputState) -> bool {
+        Player::ALL.into_iter().any(|player| self.was_pressed(player, action, state))
+    }
+
+    // a tilted stick wins over buttons; holding both movement keys cancels out
+    pub fn paddle_input(&self, player: Player, state: &InputState) -> PaddleInput {
+        let tilt = state.pad(player.pad_index()).map_or(0.0, |pad| pad.stick_tilt());
+        if tilt != 0.0 {
+            return PaddleInput::STICK((tilt * 100.0).round() as i8)
+        }
+        match (self.is_down(player, Action::MOVE_UP, state), self.is_down(player, Action::MOVE_DOWN, state)) {
+            (true, false) => PaddleInput::UP,
+            (false, true) => PaddleInput::DOWN,
+            _ => PaddleInput::IDLE
+        }
+    }
+}
+
+impl KeyMap {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    pub fn from_toml(source: &str) -> Result<Self, ConfigError> {
+        let file: KeyMapFile = toml::from_str(source)?;
+        let written = Self {left: KeyMapFile::parsed(file.left)?, right: KeyMapFile::parsed(file.right)?};
+        // the file has to make sense on its own before it is laid over the defaults
+        written.validate()?;
+        let mut keymap = Self::default();
+        for (player, bindings) in [(Player::LEFT, written.left), (Player::RIGHT, written.right)] {
+            for (action, keys) in bindings {
+                for key in &keys {
+                    keymap.unbind_key(*key);
+                }
+                keymap.bindings_mut(player).insert(action, keys);
+            }
+        }
+        Ok(keymap)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let file = KeyMapFile {left: KeyMapFile::named(&self.left), right: KeyMapFile::named(&self.right)};
+        toml::to_string(&file).expect("key maps are always representable as TOML")
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ConfigError> {
+        Ok(fs::write(path, self.to_toml())?)
+    }
+
+    // each key may only be bound once
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut seen: BTreeMap<i32, (Player, Action)> = BTreeMap::new();
+        for player in Player::ALL {
+            for (action, keys) in self.bindings(player) {
+                for key in keys {
+                    if let Some((other_player, other_action)) = seen.insert(key.code(), (player, *action)) {
+                        return Err(ConfigError::Invalid(format!(
+                            "'{key}' is bound to both {other_player} {other_action} and {player} {action}"
+                        )))
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/*
+    Rebinding.
+        Walks through a list of (player, action) slots, binding the next key pressed to each in turn.
+        Escape cancels, leaving the key map as it was when rebinding started. Keys that pause, quit
+        or rebind are refused for any other slot, since taking them away could leave no way back
+        into the pause screen or this one.
+ */
+#[derive(Debug, Clone)]
+pub struct RebindSession {
+    slots: Vec<(Player, Action)>,
+    next: usize,
+    original: KeyMap
+}
+
+impl RebindSession {
+    pub const CANCEL_KEY: Key = Key::ESCAPE;
+    const RESERVED: [Action; 3] = [Action::REBIND, Action::PAUSE, Action::QUIT];
+
+    pub fn new(players: &[Player], keymap: &KeyMap) -> Self {
+        let mut slots: Vec<(Player, Action)> = players.iter()
+            .flat_map(|player| [Action::MOVE_UP, Action::MOVE_DOWN, Action::SERVE].map(|action| (*player, action)))
+            .collect();
+        slots.push((Player::LEFT, Action::PAUSE));
+        Self {slots, next: 0, original: keymap.clone()}
+    }
+
+    // the slot waiting for a key, or None once every slot is bound
+    pub fn current(&self) -> Option<(Player, Action)> {
+        self.slots.get(self.next).copied()
+    }
+
+    pub fn progress(&self) -> (usize, usize) {
+        (self.next, self.slots.len())
+    }
+
+    // binds a freshly pressed key to the current slot; returns false if rebinding was cancelled
+    pub fn press(&mut self, key: Key, keymap: &mut KeyMap) -> bool {
+        if key == Self::CANCEL_KEY {
+            *keymap = self.original.clone();
+            return false
+        }
+        if let Some((player, action)) = self.current() {
+            let reserved = keymap.lookup(key)
+                .is_some_and(|bound| Self::RESERVED.contains(&bound.1) && bound != (player, action));
+            if reserved {
+                return true
+            }
+            keymap.bind(player, action, key);
+            self.next += 1;
+        }
+        true
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next >= self.slots.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{controller::{config::ConfigError, input::InputState}, view::input::InputEvent};
+
+    use super::{Action, Key, KeyMap, Player, RebindSession, PaddleInput};
+
+    #[test]
+    fn key_names_round_trip() {
+        for name in ["w", "up", "escape", "f1", "space", "7"] {
+            assert_eq!(Key::from_name(name).unwrap().name(), name);
+        }
+        assert_eq!(Key::from_name("W"), Some(Key(119)));
+        assert_eq!(Key::from_name("1073741913"), Some(Key(0x4000_0059)));
+        assert_eq!(Key(0x4000_0059).name(), "1073741913");
+    }
+
+    #[test]
+    fn file_overrides_defaults_and_round_trips() {
+        let keymap = KeyMap::from_toml("[left]\nmove_up = [\"i\"]\n[right]\npause = [\"q\"]").unwrap();
+        assert_eq!(keymap.keys_for(Player::LEFT, Action::MOVE_UP), &[Key(105)]);
+        assert_eq!(keymap.keys_for(Player::LEFT, Action::MOVE_DOWN), &[Key(115)]);
+        assert_eq!(keymap.lookup(Key(113)), Some((Player::RIGHT, Action::PAUSE)));
+        assert_eq!(KeyMap::from_toml(&keymap.to_toml()).unwrap(), keymap);
+    }
+
+    #[test]
+    fn rejects_keys_bound_twice() {
+        let result = KeyMap::from_toml("[left]\nmove_up = [\"x\"]\nmove_down = [\"x\"]");
+        assert!(matches!(result, Err(ConfigError::Invalid(_))));
+        assert!(matches!(KeyMap::from_toml("[left]\njump = [\"x\"]"), Err(ConfigError::Invalid(_))));
+        assert!(matches!(KeyMap::from_toml("[middle]\npause = [\"x\"]"), Err(ConfigError::Parse(_))));
+    }
+
+    #[test]
+    fn actions_follow_their_keys() {
+        let keymap = KeyMap::default();
+        let mut state = InputState::new();
+        state.apply(InputEvent::KEYDOWN(27));
+        assert!(keymap.anyone_pressed(Action::QUIT, &state));
+        assert!(!keymap.anyone_pressed(Action::PAUSE, &state));
+    }
+
+    #[test]
+    fn pads_drive_their_own_player() {
+        let keymap = KeyMap::default();
+        let mut state = InputState::new();
+        for event in [InputEvent::PADADDED(3), InputEvent::PADADDED(5), InputEvent::PADBUTTONDOWN(5, 12), InputEvent::PADAXIS(3, 1, i16::MIN)] {
+            state.apply(event);
+        }
+        assert_eq!(keymap.paddle_input(Player::LEFT, &state), PaddleInput::STICK(-100));
+        assert_eq!(keymap.paddle_input(Player::RIGHT, &state), PaddleInput::DOWN);
+        state.apply(InputEvent::PADBUTTONDOWN(3, 6));
+        assert!(keymap.anyone_pressed(Action::PAUSE, &state));
+    }
+
+    #[test]
+    fn rebinding_walks_the_slots_and_can_be_cancelled() {
+        let mut keymap = KeyMap::default();
+        let mut session = RebindSession::new(&[Player::LEFT], &keymap);
+        assert_eq!(session.current(), Some((Player::LEFT, Action::MOVE_UP)));
+        assert!(session.press(Key(105), &mut keymap));
+        assert_eq!(keymap.keys_for(Player::LEFT, Action::MOVE_UP), &[Key(105)]);
+        assert!(!session.press(Key::ESCAPE, &mut keymap));
+        assert_eq!(keymap, KeyMap::default());
+    }
+
+    #[test]
+    fn rebinding_refuses_keys_that_get_back_to_it() {
+        let mut keymap = KeyMap::default();
+        let mut session = RebindSession::new(&[Player::LEFT], &keymap);
+        let (f1, p) = (Key::from_name("f1").unwrap(), Key::from_name("p").unwrap());
+        assert!(session.press(f1, &mut keymap));
+        assert!(session.press(p, &mut keymap));
+        assert_eq!(session.current(), Some((Player::LEFT, Action::MOVE_UP)));
+        assert_eq!(keymap.keys_for(Player::LEFT, Action::REBIND), &[f1]);
+        // the pause key can still be kept for pausing
+        for key in ["i", "k", "space"] {
+            session.press(Key::from_name(key).unwrap(), &mut keymap);
+        }
+        assert_eq!(session.current(), Some((Player::LEFT, Action::PAUSE)));
+        assert!(session.press(p, &mut keymap));
+        assert!(session.is_done());
+        assert_eq!(keymap.keys_for(Player::LEFT, Action::PAUSE), &[p]);
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::VecDeque;
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{controller::options::Difficulty, model::pong::{PlayArea, game_objects::objects::{GameObject, ObjectType}, vectors::EuclideanVector}};
+
+// what a computer player gets to look at before deciding where to go
+#[derive(Clone, Copy)]
+pub struct Situation {
+    pub ball: GameObject,
+    pub paddle: GameObject,
+    // the band the ball's middle can travel in, between the insides of the top and bottom walls
+    pub top: f64,
+    pub bottom: f64
+}
+
+impl Situation {
+    pub fn new(play_area: &PlayArea, ball: &GameObject, paddle: &GameObject) -> Self {
+        let (mut top, mut bottom) = (0.0, play_area.get_height() as f64);
+        for wall in play_area.objects().iter().filter(|obj| obj.object_type == ObjectType::WALL) {
+            match wall.get_surface_normal().map(|normal| normal.y_component()) {
+                Some(y) if y > 0.5 => top = f64::max(top, wall.y_extent()),
+                Some(y) if y < -0.5 => bottom = f64::min(bottom, wall.pos.get_y_pos()),
+                _ => ()
+            }
+        }
+        let half_ball = ball.dim.get_height() / 2.0;
+        Self {ball: *ball, paddle: *paddle, top: top + half_ball, bottom: bottom - half_ball}
+    }
+
+    // the middle of the play area, which is where a paddle waits when there is nothing to do
+    pub fn center(&self) -> f64 {
+        (self.top + self.bottom) / 2.0
+    }
+
+    // is the ball on its way towards the paddle
+    pub fn ball_approaching(&self) -> bool {
+        let towards = self.paddle.midpoint().get_x_pos() - self.ball.midpoint().get_x_pos();
+        towards * self.ball.vec.x_component() > 0.0
+    }
+}
+
+/*
+    Opponents.
+        A computer player decides, every tick, where it wants the middle of its paddle to be; the
+        controller then moves the paddle there under the usual momentum model, scaled by the
+        opponent's speed factor. Opponents may be stateful (remembering what they saw, or the
+        mistake they have decided to make this rally) and must draw any randomness from a seeded
+        generator, so that a match replays the same way from the same seed.
+ */
+pub trait Opponent {
+    fn target(&mut self, situation: &Situation) -> f64;
+    // fraction of the paddle momentum caps this opponent may reach
+    fn speed_factor(&self) -> f64;
+}
+
+// the knobs every tier is built from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpponentTuning {
+    // how many ticks old the ball position the opponent reacts to is
+    pub reaction_ticks: usize,
+    // largest distance, in pixels, by which the opponent misjudges where to be; drawn once per approach
+    pub aim_error: f64,
+    pub speed_factor: f64
+}
+
+impl OpponentTuning {
+    pub fn for_difficulty(difficulty: Difficulty) -> Self {
+        let speed_factor = difficulty.speed_factor();
+        match difficulty {
+            Difficulty::EASY    => Self {reaction_ticks: 24, aim_error: 40.0, speed_factor},
+            Difficulty::NORMAL  => Self {reaction_ticks: 6, aim_error: 10.0, speed_factor},
+            Difficulty::HARD    => Self {reaction_ticks: 0, aim_error: 4.0, speed_factor},
+        }
+    }
+}
+
+impl Difficulty {
+    // easier tiers chase the ball they remember; the hardest works out where it will arrive
+    pub fn opponent(&self, seed: u64) -> Box<dyn Opponent> {
+        let tuning = OpponentTuning::for_difficulty(*self);
+        match self {
+            Difficulty::EASY | Difficulty::NORMAL => Box::new(ReactiveOpponent::new(tuning, seed)),
+            Difficulty::HARD => Box::new(PredictiveOpponent::new(tuning, seed)),
+        }
+    }
+}
+
+// picks a fresh aim error whenever the ball turns towards the paddle, and keeps it until the next approach
+struct AimError {
+    max: f64,
+    current: f64,
+    was_approaching: bool,
+    rng: StdRng
+}
+
+impl AimError {
+    fn new(max: f64, seed: u64) -> Self {
+        Self {max, current: 0.0, was_approaching: false, rng: StdRng::seed_from_u64(seed)}
+    }
+
+    fn sample(&mut self, approaching: bool) -> f64 {
+        if approaching && !self.was_approaching && self.max > 0.0 {
+            self.current = self.rng.gen_range(-self.max..=self.max);
+        }
+        self.was_approaching = approaching;
+        self.current
+    }
+}
+
+// follows where the ball was a little while ago, missing by a random amount
+pub struct ReactiveOpponent {
+    tuning: OpponentTuning,
+    seen: VecDeque<f64>,
+    error: AimError
+}
+
+impl ReactiveOpponent {
+    pub fn new(tuning: OpponentTuning, seed: u64) -> Self {
+        Self {tuning, seen: VecDeque::with_capacity(tuning.reaction_ticks + 1), error: AimError::new(tuning.aim_error, seed)}
+    }
+}
+
+impl Opponent for ReactiveOpponent {
+    fn target(&mut self, situation: &Situation) -> f64 {
+        self.seen.push_back(situation.ball.midpoint().get_y_pos());
+        while self.seen.len() > self.tuning.reaction_ticks + 1 {
+            self.seen.pop_front();
+        }
+        let remembered = self.seen[0];
+        remembered + self.error.sample(situation.ball_approaching())
+    }
+
+    fn speed_factor(&self) -> f64 {
+        self.tuning.speed_factor
+    }
+}
+
+// works out where the ball will cross the paddle's face, bounces and all, and waits there
+pub struct PredictiveOpponent {
+    tuning: OpponentTuning,
+    error: AimError
+}
+
+impl PredictiveOpponent {
+    // more bounces than this between the ball and the paddle and the ball is too flat to bother
+    const MAX_BOUNCES: usize = 32;
+
+    pub fn new(tuning: OpponentTuning, seed: u64) -> Self {
+        Self {tuning, error: AimError::new(tuning.aim_error, seed)}
+    }
+
+    // height of the ball's middle when it reaches the paddle, bouncing off the walls on the way
+    pub fn intercept(situation: &Situation) -> Option<f64> {
+        let (ball, paddle) = (&situation.ball, &situation.paddle);
+        let half_ball = ball.dim.get_width() / 2.0;
+        let mut vec = ball.vec;
+        let (mut x, mut y) = (ball.midpoint().get_x_pos(), ball.midpoint().get_y_pos());
+        let face = if vec.x_component() > 0.0 {paddle.pos.get_x_pos() - half_ball} else {paddle.x_extent() + half_ball};
+
+        for _ in 0..=Self::MAX_BOUNCES {
+            let (dx, dy) = (vec.x_component(), vec.y_component());
+            if dx == 0.0 {
+                return None
+            }
+            let to_face = (face - x) / dx;
+            if to_face < 0.0 {
+                return None
+            }
+            let wall = if dy > 0.0 {situation.bottom} else {situation.top};
+            let to_wall = if dy == 0.0 {f64::INFINITY} else {(wall - y) / dy};
+            if to_face <= to_wall {
+                return Some(y + dy * to_face)
+            }
+            x += dx * to_wall;
+            y = wall;
+            let normal = EuclideanVector::from_components(0.0, -dy.signum());
+            vec.reflect(normal);
+        }
+        None
+    }
+}
+
+impl Opponent for PredictiveOpponent {
+    fn target(&mut self, situation: &Situation) -> f64 {
+        let approaching = situation.ball_approaching();
+        let error = self.error.sample(approaching);
+        match Self::intercept(situation) {
+            Some(y) if approaching => y + error,
+            _ => situation.center()
+        }
+    }
+
+    fn speed_factor(&self) -> f64 {
+        self.tuning.speed_factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{controller::options::Difficulty, model::pong::{game_objects::{GameObjectFactory, objects::ObjectType}, vectors::EuclideanVector}};
+
+    use super::{Situation, PredictiveOpponent, ReactiveOpponent, OpponentTuning, Opponent};
+
+    fn situation(ball_at: (usize, usize), vec: EuclideanVector) -> Situation {
+        let mut factory = GameObjectFactory::new();
+        let mut ball = factory.create(ObjectType::BALL, ball_at.0, ball_at.1, 10, 10);
+        ball.vec = vec;
+        let paddle = factory.create(ObjectType::PADDLE, 500, 40, 10, 60);
+        Situation {ball, paddle, top: 5.0, bottom: 195.0}
+    }
+
+    #[test]
+    fn predicts_intercept_through_a_bounce() {
+        // heading down and right at 45 degrees from (100, 150) towards a face at x = 495: the ball
+        // bounces off the bottom at x = 145, off the top at x = 335, and has 160 left to fall
+        let situation = situation((95, 145), EuclideanVector::from_components(2.0, 2.0));
+        let y = PredictiveOpponent::intercept(&situation).unwrap();
+        assert!((y - 165.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn reacts_to_where_the_ball_was() {
+        let tuning = OpponentTuning {reaction_ticks: 2, aim_error: 0.0, speed_factor: 1.0};
+        let mut opponent = ReactiveOpponent::new(tuning, 0);
+        let targets: Vec<f64> = [50, 60, 70, 80].iter()
+            .map(|y| opponent.target(&situation((100, *y), EuclideanVector::from_components(1.0, 0.0))))
+            .collect();
+        assert_eq!(targets, vec![55.0, 55.0, 55.0, 65.0]);
+    }
+
+    #[test]
+    fn mistakes_are_reproducible_from_the_seed() {
+        let run = |seed| {
+            let mut opponent = Difficulty::EASY.opponent(seed);
+            let s = situation((100, 100), EuclideanVector::from_components(1.0, 0.5));
+            (0..5).map(|_| opponent.target(&s)).collect::<Vec<f64>>()
+        };
+        assert_eq!(run(9), run(9));
+        assert_ne!(run(9), run(10));
+    }
+
+    #[test]
+    fn hard_waits_in_the_middle_while_the_ball_leaves() {
+        let mut opponent = Difficulty::HARD.opponent(1);
+        let s = situation((100, 30), EuclideanVector::from_components(-1.0, 0.5));
+        assert_eq!(opponent.target(&s), s.center());
+    }
+}
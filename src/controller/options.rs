@@ -0,0 +1,139 @@
+use std::{fmt::Display, path::PathBuf, str::FromStr};
+
+use crate::controller::{keymap::KeyMap, replay::Replay};
+
+// who is in control of each paddle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    // a human on the left against the computer on the right
+    SOLO,
+    // two humans sharing a keyboard
+    VERSUS,
+    // the computer against itself
+    DEMO
+}
+
+impl Display for GameMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameMode::SOLO      => write!(f, "solo"),
+            GameMode::VERSUS    => write!(f, "versus"),
+            GameMode::DEMO      => write!(f, "demo"),
+        }
+    }
+}
+
+impl FromStr for GameMode {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "solo" | "1p"       => Ok(GameMode::SOLO),
+            "versus" | "2p"     => Ok(GameMode::VERSUS),
+            "demo" | "cpu"      => Ok(GameMode::DEMO),
+            _ => Err(format!("unknown game mode '{s}', expected one of: solo (1p), versus (2p), demo (cpu)"))
+        }
+    }
+}
+
+// what the left paddle's player steers it with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaddleControl {
+    // keys or a game controller
+    KEYBOARD,
+    // the paddle chases the mouse pointer
+    MOUSE
+}
+
+impl Display for PaddleControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaddleControl::KEYBOARD => write!(f, "keyboard"),
+            PaddleControl::MOUSE    => write!(f, "mouse"),
+        }
+    }
+}
+
+impl FromStr for PaddleControl {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "keyboard" | "keys" => Ok(PaddleControl::KEYBOARD),
+            "mouse"             => Ok(PaddleControl::MOUSE),
+            _ => Err(format!("unknown paddle control '{s}', expected one of: keyboard, mouse"))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    EASY,
+    NORMAL,
+    HARD
+}
+
+impl Difficulty {
+    // fraction of the paddle momentum caps the computer is allowed to reach
+    pub fn speed_factor(&self) -> f64 {
+        match self {
+            Difficulty::EASY    => 0.6,
+            Difficulty::NORMAL  => 1.0,
+            Difficulty::HARD    => 1.4,
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Difficulty::EASY    => write!(f, "easy"),
+            Difficulty::NORMAL  => write!(f, "normal"),
+            Difficulty::HARD    => write!(f, "hard"),
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "easy"      => Ok(Difficulty::EASY),
+            "normal"    => Ok(Difficulty::NORMAL),
+            "hard"      => Ok(Difficulty::HARD),
+            _ => Err(format!("unknown difficulty '{s}', expected one of: easy, normal, hard"))
+        }
+    }
+}
+
+/*
+    Everything about a single session that isn't a rule of the game.
+        A seed of None is replaced by a random one when the match starts. When playing back a
+        replay, its seed and inputs take the place of the seed and the keyboard. Keys rebound
+        during the match are saved to keymap_path, when there is one.
+ */
+pub struct MatchOptions {
+    pub mode: GameMode,
+    pub difficulty: Difficulty,
+    pub control: PaddleControl,
+    pub keymap: KeyMap,
+    pub keymap_path: Option<PathBuf>,
+    pub fullscreen: bool,
+    pub seed: Option<u64>,
+    pub record: Option<PathBuf>,
+    pub playback: Option<Replay>
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self {
+            mode: GameMode::SOLO,
+            difficulty: Difficulty::NORMAL,
+            control: PaddleControl::KEYBOARD,
+            keymap: KeyMap::default(),
+            keymap_path: None,
+            fullscreen: false,
+            seed: None,
+            record: None,
+            playback: None
+        }
+    }
+}
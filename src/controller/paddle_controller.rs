@@ -0,0 +1,168 @@
+use crate::{controller::{input::{InputState, PaddleInput}, keymap::{KeyMap, Player}, opponent::{Opponent, Situation}, replay::Replay}, model::pong::{PlayArea, game_objects::objects::{GameObject, ObjectType}}};
+
+// everything a paddle controller may look at when deciding what to do this tick
+pub struct Snapshot<'a> {
+    pub play_area: &'a PlayArea,
+    pub input: &'a InputState,
+    pub keymap: &'a KeyMap,
+    // whose paddle is being driven
+    pub player: Player
+}
+
+impl<'a> Snapshot<'a> {
+    pub fn paddle(&self) -> &'a GameObject {
+        self.play_area.by_role(self.player.paddle()).expect("every match has both paddles")
+    }
+
+    // the ball the paddle most needs to worry about: the nearest of those coming its way, if any are
+    pub fn ball(&self) -> Option<&'a GameObject> {
+        let paddle_x = self.paddle().midpoint().get_x_pos();
+        let distance = |ball: &GameObject| (paddle_x - ball.midpoint().get_x_pos()).abs();
+        let approaching = |ball: &GameObject| (paddle_x - ball.midpoint().get_x_pos()) * ball.vec.x_component() > 0.0;
+        self.play_area.objects().iter()
+            .filter(|obj| obj.object_type == ObjectType::BALL)
+            .min_by(|a, b| approaching(b).cmp(&approaching(a)).then(distance(a).total_cmp(&distance(b))))
+    }
+}
+
+// what a paddle controller wants its paddle to do this tick
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Intent {
+    // a key press, stick tilt or replayed input, moving the paddle as a human would
+    INPUT(PaddleInput),
+    // head for this height with the middle of the paddle, at up to this fraction of the momentum caps
+    TOWARD(f64, f64)
+}
+
+/*
+    Paddle controllers.
+        Each paddle is driven by a controller, asked once per tick what it wants its paddle to do.
+        Controllers only get to look at the play area, never change it; the match moves the paddle
+        under the momentum model, so any controller can drive either side. A controller returns
+        None once it has nothing left to give (a replay that has run out), which ends the match.
+        Whatever a controller remembers from one match it forgets when told a new one is starting,
+        so that a rematch plays out as the same match would from a fresh start.
+ */
+pub trait PaddleController {
+    fn intent(&mut self, snapshot: &Snapshot) -> Option<Intent>;
+
+    fn new_match(&mut self) {}
+}
+
+// moves the paddle with the player's bindings in the key map, or their game controller's stick
+pub struct KeyboardController;
+
+impl PaddleController for KeyboardController {
+    fn intent(&mut self, snapshot: &Snapshot) -> Option<Intent> {
+        Some(Intent::INPUT(snapshot.keymap.paddle_input(snapshot.player, snapshot.input)))
+    }
+}
+
+/*
+    Mouse control.
+        The paddle is steered like an analog stick tilted in proportion to how far its middle is
+        from the pointer, reaching full tilt half a paddle away. It speeds up and slows down under
+        the same momentum caps as every other paddle, so it can't jump to the pointer, and eases
+        in rather than overshooting.
+ */
+pub struct PointerController;
+
+impl PaddleController for PointerController {
+    fn intent(&mut self, snapshot: &Snapshot) -> Option<Intent> {
+        let Some((_, pointer_y)) = snapshot.input.get_pointer() else {return Some(Intent::INPUT(PaddleInput::IDLE))};
+        let paddle = snapshot.paddle();
+        let distance = pointer_y as f64 - paddle.midpoint().get_y_pos();
+        let tilt = (distance / (paddle.dim.get_height() / 2.0)).clamp(-1.0, 1.0);
+        Some(Intent::INPUT(match (tilt * 100.0).round() as i8 {
+            0 => PaddleInput::IDLE,
+            tilt => PaddleInput::STICK(tilt)
+        }))
+    }
+}
+
+// hands the paddle to a computer opponent, made afresh for every match so that it starts from its seed again
+pub struct AiController {
+    make: Box<dyn Fn() -> Box<dyn Opponent>>,
+    opponent: Box<dyn Opponent>
+}
+
+impl AiController {
+    pub fn new(make: impl Fn() -> Box<dyn Opponent> + 'static) -> Self {
+        Self {opponent: make(), make: Box::new(make)}
+    }
+}
+
+impl PaddleController for AiController {
+    fn intent(&mut self, snapshot: &Snapshot) -> Option<Intent> {
+        let Some(ball) = snapshot.ball() else {return Some(Intent::INPUT(PaddleInput::IDLE))};
+        let situation = Situation::new(snapshot.play_area, ball, snapshot.paddle());
+        Some(Intent::TOWARD(self.opponent.target(&situation), self.opponent.speed_factor()))
+    }
+
+    fn new_match(&mut self) {
+        self.opponent = (self.make)();
+    }
+}
+
+// plays back recorded inputs, one per tick, until they run out
+pub struct ReplayController {
+    replay: Replay,
+    next: usize
+}
+
+impl ReplayController {
+    pub fn new(replay: Replay) -> Self {
+        Self {replay, next: 0}
+    }
+}
+
+impl PaddleController for ReplayController {
+    fn intent(&mut self, _snapshot: &Snapshot) -> Option<Intent> {
+        let input = self.replay.input_at(self.next)?;
+        self.next += 1;
+        Some(Intent::INPUT(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{controller::{config::GameConfig, input::{InputState, PaddleInput}, keymap::{KeyMap, Player}, opponent::Opponent, replay::Replay}, model::pong::{PlayArea, game_objects::GameObjectFactory}};
+
+    use super::{Snapshot, Intent, PaddleController, ReplayController, AiController};
+
+    struct Fixed(f64);
+
+    impl Opponent for Fixed {
+        fn target(&mut self, _situation: &crate::controller::opponent::Situation) -> f64 {
+            self.0
+        }
+
+        fn speed_factor(&self) -> f64 {
+            0.5
+        }
+    }
+
+    fn play_area() -> PlayArea {
+        crate::controller::pong_controller::init_playarea(640, 480, &GameConfig::default(), &mut GameObjectFactory::new()).unwrap()
+    }
+
+    #[test]
+    fn replay_runs_out() {
+        let (play_area, input, keymap) = (play_area(), InputState::new(), KeyMap::default());
+        let snapshot = Snapshot {play_area: &play_area, input: &input, keymap: &keymap, player: Player::RIGHT};
+        let mut replay = Replay::new(0, (640, 480));
+        replay.push(PaddleInput::UP);
+        replay.push(PaddleInput::DOWN);
+        let mut controller = ReplayController::new(replay);
+        let intents: Vec<Option<Intent>> = (0..3).map(|_| controller.intent(&snapshot)).collect();
+        assert_eq!(intents, vec![Some(Intent::INPUT(PaddleInput::UP)), Some(Intent::INPUT(PaddleInput::DOWN)), None]);
+    }
+
+    #[test]
+    fn ai_passes_on_its_opponents_target() {
+        let (play_area, input, keymap) = (play_area(), InputState::new(), KeyMap::default());
+        let snapshot = Snapshot {play_area: &play_area, input: &input, keymap: &keymap, player: Player::LEFT};
+        let mut controller = AiController::new(|| Box::new(Fixed(120.0)));
+        assert_eq!(controller.intent(&snapshot), Some(Intent::TOWARD(120.0, 0.5)));
+    }
+}
@@ -1,10 +1,61 @@
-use std::{thread::{JoinHandle, self}, sync::{Arc, Mutex, atomic::{AtomicBool, Ordering, AtomicI32}}};
+use std::{fmt::Display, thread::{JoinHandle, self}, sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}, mpsc::{self, Sender, Receiver}}, time::Duration, path::PathBuf};
 
-use crate::{model::pong::{PlayArea, game_objects::{objects::{GameObject, ObjectType}, ObjectDimensions, GameObjectFactory, Position}, vectors::EuclideanVector}, view::{gameview::{GameView}, assets::{Drawable, rectangle::Rectangle, drawable::{Point, Dimensions}, color::Colors, line::Line}}};
+use rand::{Rng, SeedableRng, rngs::StdRng};
 
-pub fn run(width: usize, height: usize) {
+use crate::{controller::{clock::GameClock, config::{GameConfig, ConfigError, ServeRule}, input::{PaddleInput, InputState}, keymap::{Action, Key, KeyMap, Player, RebindSession}, paddle_controller::{PaddleController, Snapshot, Intent, KeyboardController, PointerController, AiController, ReplayController}, options::{MatchOptions, GameMode, Difficulty, PaddleControl}, replay::{Replay, ReplayError}, state::{GameState, Transition}}, model::pong::{PlayArea, PlayAreaError, ObjectHandle, Role, game_objects::{objects::{GameObject, ObjectType}, ObjectDimensions, GameObjectFactory, behaviors::English}, vectors::EuclideanVector, collision::Manifold}, view::{gameview::{GameView}, input::InputEvent, assets::{Drawable, rectangle::Rectangle, drawable::{Point, Dimensions}, color::Colors, line::{Line, DashedLine}, digits::SevenSegmentDigits, text::{Text, Align}, polygon::Polygon, style::{RenderStyle, Shape}}}};
+
+// plays a match in a window, saving the human's inputs afterwards if asked to record them
+pub fn run(width: usize, height: usize, config: GameConfig, options: MatchOptions) -> Result<(), MatchError> {
     unsafe {
-        default(width, height).run();
+        Ok(with_options(width, height, config, options, false)?.run()?)
+    }
+}
+
+// plays a match with both paddles on autopilot and no window, stopping at a win or after tick_budget ticks
+pub fn run_headless(width: usize, height: usize, config: GameConfig, tick_budget: u64, seed: u64) -> Result<MatchResult, MatchError> {
+    Ok(headless(width, height, config, seed)?.run_headless(tick_budget))
+}
+
+// as run_headless, but with the paddles driven as the options say; a solo match needs a replay to play back
+pub fn run_headless_with(width: usize, height: usize, config: GameConfig, options: MatchOptions, tick_budget: u64) -> Result<MatchResult, MatchError> {
+    Ok(with_options(width, height, config, options, true)?.run_headless(tick_budget))
+}
+
+// why a match could not be set up, or its recording saved
+#[derive(Debug)]
+pub enum MatchError {
+    Config(ConfigError),
+    PlayArea(PlayAreaError),
+    Replay(ReplayError)
+}
+
+impl Display for MatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchError::Config(e)       => write!(f, "{e}"),
+            MatchError::PlayArea(e)     => write!(f, "could not set up the play area: {e}"),
+            MatchError::Replay(e)       => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for MatchError {}
+
+impl From<ConfigError> for MatchError {
+    fn from(e: ConfigError) -> Self {
+        MatchError::Config(e)
+    }
+}
+
+impl From<PlayAreaError> for MatchError {
+    fn from(e: PlayAreaError) -> Self {
+        MatchError::PlayArea(e)
+    }
+}
+
+impl From<ReplayError> for MatchError {
+    fn from(e: ReplayError) -> Self {
+        MatchError::Replay(e)
     }
 }
 
@@ -16,23 +67,245 @@ fn convert_usize_tuple_to_i32_tuple(tup: (usize, usize)) -> (i32, i32) {
     (tup.0 as i32, tup.1 as i32)
 }
 
-fn convert_game_object_to_drawing(id: usize, obj: &GameObject) -> Box<dyn Drawable> {
-    let (color, priority) = match obj.object_type {
-        ObjectType::BALL    => (Colors::YELLOW.as_rgb()     , 1),
-        ObjectType::PADDLE  => (Colors::WHITE.as_rgb()      , 1),
-        ObjectType::WALL    => (Colors::GREY.as_rgb()       , 3),
-        ObjectType::GOAL    => (Colors::BLACK.as_rgb(), 2),
-        ObjectType::CUSTOM  => (Colors::RED.as_rgb()        , 1),
-    };
-    Box::new(Rectangle::new(
+/*
+    Render styles.
+        How each type of game object is drawn. The ball is round, as it always was in the arcade,
+        though the model still treats it as a box. The shapes can be picked in the match rules; the
+        colors and layering can't.
+ */
+#[derive(Clone, Copy)]
+pub struct RenderStyles {
+    pub ball: RenderStyle,
+    pub paddle: RenderStyle,
+    pub wall: RenderStyle,
+    pub goal: RenderStyle,
+    pub custom: RenderStyle
+}
+
+impl RenderStyles {
+    pub fn from_config(config: &GameConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            ball:   RenderStyle {shape: config.ball_shape, ..defaults.ball},
+            paddle: RenderStyle {shape: config.paddle_shape, ..defaults.paddle},
+            wall:   RenderStyle {shape: config.wall_shape, ..defaults.wall},
+            goal:   RenderStyle {shape: config.goal_shape, ..defaults.goal},
+            ..defaults
+        }
+    }
+
+    pub fn for_type(&self, object_type: ObjectType) -> RenderStyle {
+        match object_type {
+            ObjectType::BALL    => self.ball,
+            ObjectType::PADDLE  => self.paddle,
+            ObjectType::WALL    => self.wall,
+            ObjectType::GOAL    => self.goal,
+            ObjectType::CUSTOM  => self.custom
+        }
+    }
+}
+
+impl Default for RenderStyles {
+    fn default() -> Self {
+        Self {
+            ball:   RenderStyle::new(Shape::CIRCLE, Colors::YELLOW.as_rgb(), 1),
+            paddle: RenderStyle::new(Shape::RECTANGLE, Colors::WHITE.as_rgb(), 1),
+            wall:   RenderStyle::new(Shape::RECTANGLE, Colors::GREY.as_rgb(), 3),
+            goal:   RenderStyle::new(Shape::RECTANGLE, Colors::BLACK.as_rgb(), 2),
+            custom: RenderStyle::new(Shape::RECTANGLE, Colors::RED.as_rgb(), 1)
+        }
+    }
+}
+
+fn convert_game_object_to_drawing(id: usize, obj: &GameObject, alpha: f64, styles: &RenderStyles) -> Box<dyn Drawable> {
+    styles.for_type(obj.object_type).drawing(
+        id,
+        Point::from_tuple(convert_usize_tuple_to_i32_tuple(obj.interpolated_pos(alpha).as_discrete_tuple())),
+        object_dimensions_to_dimensions(&obj.dim)
+    )
+}
+
+// the net down the middle, behind everything but the walls
+fn create_net_drawing(id: usize, area: (usize, usize)) -> Box<dyn Drawable> {
+    const DASH: i32 = 12;
+    let (width, height) = convert_usize_tuple_to_i32_tuple(area);
+    Box::new(DashedLine::new(id, Point::new(width / 2, 0), Point::new(width / 2, height), DASH, DASH, Colors::GREY.as_rgb(), 2))
+}
+
+// an arrow from one point to another, with its head at the second
+fn create_arrow_drawing(id: usize, from: (i32, i32), to: (i32, i32)) -> Box<dyn Drawable> {
+    const HEAD: f64 = 10.0;
+    let (dx, dy) = ((to.0 - from.0) as f64, (to.1 - from.1) as f64);
+    let length = (dx * dx + dy * dy).sqrt().max(1.0);
+    let (ux, uy) = (dx / length * HEAD, dy / length * HEAD);
+    let barb = |side: f64| Point::new(to.0 - (ux - uy * side) as i32, to.1 - (uy + ux * side) as i32);
+    Box::new(Line::new(
         id,
-        Point::from_tuple(convert_usize_tuple_to_i32_tuple(obj.pos.as_discrete_tuple())),
-        object_dimensions_to_dimensions(&obj.dim),
-        color,
-        priority
+        vec![Point::from_tuple(from), Point::from_tuple(to), barb(1.0), Point::from_tuple(to), barb(-1.0)],
+        Colors::CYAN.as_rgb(),
+        -1
     ))
 }
 
+/*
+    Rebinding screen.
+        Without text to lean on, the prompt is drawn: a row of squares across the top shows how far
+        through the bindings the player is, the paddle whose key is wanted is outlined, and a symbol
+        over it shows which action the key will perform (an arrow up or down to move, an arrow towards
+        the middle to serve, two bars to pause).
+ */
+fn create_rebind_drawings(first_id: usize, prompt: &RebindPrompt, area: (usize, usize)) -> Vec<Box<dyn Drawable>> {
+    const SLOT: i32 = 16;
+    const MARGIN: i32 = 6;
+    let (width, _) = convert_usize_tuple_to_i32_tuple(area);
+    let mut drawings: Vec<Box<dyn Drawable>> = Vec::new();
+    let mut id = first_id;
+    let mut next_id = || {id += 1; id - 1};
+
+    let (done, total) = prompt.progress;
+    let row_x = (width - total as i32 * 2 * SLOT) / 2;
+    for slot in 0..total {
+        let color = if slot < done {Colors::WHITE} else if slot == done {Colors::YELLOW} else {Colors::GREY};
+        drawings.push(Box::new(Rectangle::new(
+            next_id(),
+            Point::new(row_x + slot as i32 * 2 * SLOT, 3 * SLOT),
+            Dimensions::new(SLOT, SLOT),
+            color.as_rgb(),
+            -1
+        )));
+    }
+
+    let paddle = &prompt.paddle;
+    let (x, y) = convert_usize_tuple_to_i32_tuple(paddle.pos.as_discrete_tuple());
+    let (w, h) = (paddle.dim.get_width() as i32, paddle.dim.get_height() as i32);
+    let (left, top, right, bottom) = (x - MARGIN, y - MARGIN, x + w + MARGIN, y + h + MARGIN);
+    drawings.push(create_outline_drawing(next_id(), (left, top, right, bottom), Colors::CYAN));
+
+    let center = (x + w / 2, y + h / 2);
+    match prompt.action {
+        Action::MOVE_UP => drawings.push(create_arrow_drawing(next_id(), (center.0, center.1 + h / 4), (center.0, top - 4 * SLOT))),
+        Action::MOVE_DOWN => drawings.push(create_arrow_drawing(next_id(), (center.0, center.1 - h / 4), (center.0, bottom + 4 * SLOT))),
+        Action::SERVE => drawings.push(create_arrow_drawing(next_id(), center, (center.0 + (width / 2 - center.0).signum() * 6 * SLOT, center.1))),
+        _ => drawings.extend(create_pause_drawings(next_id(), area))
+    }
+    drawings
+}
+
+// two bars in the middle of the play area; uses two ids from first_id on
+fn create_pause_drawings(first_id: usize, area: (usize, usize)) -> Vec<Box<dyn Drawable>> {
+    const SLOT: i32 = 16;
+    let (width, height) = convert_usize_tuple_to_i32_tuple(area);
+    [-SLOT, SLOT / 2].iter().enumerate().map(|(i, offset)| Box::new(Rectangle::new(
+        first_id + i,
+        Point::new(width / 2 + offset, height / 2 - 2 * SLOT),
+        Dimensions::new(SLOT / 2, 4 * SLOT),
+        Colors::CYAN.as_rgb(),
+        -1
+    )) as Box<dyn Drawable>).collect()
+}
+
+fn create_outline_drawing(id: usize, (left, top, right, bottom): (i32, i32, i32, i32), color: Colors) -> Box<dyn Drawable> {
+    Box::new(Line::new(
+        id,
+        vec![Point::new(left, top), Point::new(right, top), Point::new(right, bottom), Point::new(left, bottom), Point::new(left, top)],
+        color.as_rgb(),
+        -1
+    ))
+}
+
+// a triangle pointing right, the usual sign for "press to start"
+fn create_play_drawing(id: usize, (x, y): (i32, i32), size: i32) -> Box<dyn Drawable> {
+    Box::new(Polygon::new(
+        id,
+        vec![Point::new(x - size / 2, y - size), Point::new(x + size, y), Point::new(x - size / 2, y + size)],
+        Colors::CYAN.as_rgb(),
+        -1
+    ))
+}
+
+/*
+    State screens.
+        Drawn for the same reason as the rebinding screen: a play sign in the middle on the title,
+        a frame around the waiting ball with an arrow towards the receiver while serving, two bars
+        while paused, a bar across the scorer's half under their score after a point, and, once the
+        match is over, the winner's half framed with the play sign in the middle for a rematch.
+        Every state but PLAYING is also named in text below the middle.
+ */
+fn create_state_drawings(first_id: usize, state: GameState, area: (usize, usize)) -> Vec<Box<dyn Drawable>> {
+    const SIZE: i32 = 32;
+    let (width, height) = convert_usize_tuple_to_i32_tuple(area);
+    let mut drawings = create_state_shapes(first_id, state, area);
+    // the state named under the middle of the screen, the title naming the game instead
+    let label = match state {
+        GameState::TITLE => String::from("pong"),
+        GameState::PLAYING => return drawings,
+        _ => state.to_string()
+    };
+    drawings.push(Box::new(Text::new(
+        first_id + drawings.len(),
+        Point::new(width / 2, height / 2 + 3 * SIZE),
+        label,
+        SIZE / 2,
+        Align::CENTER,
+        Colors::CYAN.as_rgb(),
+        -1
+    )));
+    drawings
+}
+
+fn create_state_shapes(first_id: usize, state: GameState, area: (usize, usize)) -> Vec<Box<dyn Drawable>> {
+    const SIZE: i32 = 32;
+    let (width, height) = convert_usize_tuple_to_i32_tuple(area);
+    let center = (width / 2, height / 2);
+    let half = |player: Player| match player {
+        Player::LEFT => (0, width / 2),
+        Player::RIGHT => (width / 2, width)
+    };
+    match state {
+        GameState::TITLE => vec![create_play_drawing(first_id, center, SIZE)],
+        GameState::SERVING(toward, _) => {
+            let across = match toward {
+                Player::LEFT => -1,
+                Player::RIGHT => 1
+            };
+            vec![
+                create_outline_drawing(first_id, (center.0 - SIZE, center.1 - SIZE, center.0 + SIZE, center.1 + SIZE), Colors::CYAN),
+                create_arrow_drawing(first_id + 1, (center.0 + across * SIZE, center.1), (center.0 + across * 4 * SIZE, center.1))
+            ]
+        },
+        GameState::PAUSED => create_pause_drawings(first_id, area),
+        GameState::POINT_SCORED(scorer, _) => {
+            let (left, right) = half(scorer);
+            vec![Box::new(Rectangle::new(
+                first_id,
+                Point::new(left + SIZE, 3 * SIZE),
+                Dimensions::new(right - left - 2 * SIZE, SIZE / 2),
+                Colors::YELLOW.as_rgb(),
+                -1
+            ))]
+        },
+        GameState::GAME_OVER(winner) => {
+            let (left, right) = half(winner);
+            vec![
+                create_outline_drawing(first_id, (left + SIZE, SIZE, right - SIZE, height - SIZE), Colors::YELLOW),
+                create_play_drawing(first_id + 1, center, SIZE)
+            ]
+        },
+        GameState::PLAYING => Vec::new()
+    }
+}
+
+// each player's score, centred over their half near the top; uses two ids from first_id on
+fn create_score_drawings(first_id: usize, (left, right): (u8, u8), area: (usize, usize)) -> Vec<Box<dyn Drawable>> {
+    const DIGIT: Dimensions = Dimensions {w: 24, h: 48};
+    const THICKNESS: i32 = 6;
+    const TOP: i32 = 24;
+    let (width, _) = convert_usize_tuple_to_i32_tuple(area);
+    [(left, width / 4), (right, 3 * width / 4)].iter().enumerate().map(|(i, (score, center))| Box::new(
+        SevenSegmentDigits::new(first_id + i, Point::new(*center, TOP), score.to_string(), DIGIT, THICKNESS, Colors::WHITE.as_rgb(), 2).centred()
+    ) as Box<dyn Drawable>).collect()
+}
+
 fn create_vector_drawing(id: usize, obj: &GameObject) -> Box<dyn Drawable> {
     Box::new(Line::new(
         id,
@@ -45,45 +318,51 @@ fn create_vector_drawing(id: usize, obj: &GameObject) -> Box<dyn Drawable> {
     ))
 }
 
-fn add_walls(play_area: &mut PlayArea, factory: &mut GameObjectFactory, wall_thickness: usize) {
+fn add_walls(play_area: &mut PlayArea, factory: &mut GameObjectFactory, wall_thickness: usize) -> Result<(), PlayAreaError> {
     let wall_specs = [
-        // (x, y, w, h, angle)
+        // (x, y, w, h, angle of the normal facing into the play area)
         (0, 0, play_area.get_width(), wall_thickness, 90.0),
         (0, play_area.get_height() - wall_thickness, play_area.get_width(), wall_thickness, 270.0),
-        (0, 0, wall_thickness, play_area.get_height(), 180.0),
-        (play_area.get_width() - wall_thickness, 0, wall_thickness, play_area.get_height(), 0.0)
+        (0, 0, wall_thickness, play_area.get_height(), 0.0),
+        (play_area.get_width() - wall_thickness, 0, wall_thickness, play_area.get_height(), 180.0)
     ];
 
     for (x, y, w, h, angle) in wall_specs {
         let mut wall = factory.create(ObjectType::WALL, x, y, w, h);
-        wall.vec.set_angle(angle);
-        play_area.add_game_object(wall)
+        wall.set_surface_normal(EuclideanVector::new(1.0, angle));
+        play_area.add_game_object(wall)?;
     }
+    Ok(())
 }
 
-fn paddle_interact_behavior(paddle: &mut GameObject, other: &GameObject) {
+fn paddle_interact_behavior(paddle: &mut GameObject, other: &GameObject, _manifold: &Manifold) {
     match other.object_type {
         ObjectType::WALL => paddle.kill_velocity(),
         _ => ()
     }
 }
 
-fn add_paddles(play_area: &mut PlayArea, factory: &mut GameObjectFactory, paddle_dims: (usize, usize), wall_thickness: usize, gap: usize) {
+fn add_paddles(play_area: &mut PlayArea, factory: &mut GameObjectFactory, paddle_dims: (usize, usize), wall_thickness: usize, gap: usize, english: English) -> Result<(), PlayAreaError> {
     let mut left_paddle = factory.create(ObjectType::PADDLE, wall_thickness + gap, wall_thickness + gap, paddle_dims.0, paddle_dims.1);
-    let right_paddle = factory.create(ObjectType::PADDLE, play_area.get_width() - (wall_thickness + paddle_dims.0 + gap), wall_thickness + gap, paddle_dims.0, paddle_dims.1);
+    let mut right_paddle = factory.create(ObjectType::PADDLE, play_area.get_width() - (wall_thickness + paddle_dims.0 + gap), wall_thickness + gap, paddle_dims.0, paddle_dims.1);
     
     left_paddle.behavior.set_interact_behavior(paddle_interact_behavior);
+    left_paddle.set_english(english);
+    right_paddle.set_english(english);
 
-    play_area.add_game_object(left_paddle);
-    play_area.add_game_object(right_paddle);
+    play_area.add_with_role(left_paddle, Role::LEFT_PADDLE)?;
+    play_area.add_with_role(right_paddle, Role::RIGHT_PADDLE)?;
+    Ok(())
 }
 
-fn add_ball(play_area: &mut PlayArea, factory: &mut GameObjectFactory, ball_width: usize) {
+fn add_ball(play_area: &mut PlayArea, factory: &mut GameObjectFactory, ball_width: usize, serve_speed: f64) -> Result<(), PlayAreaError> {
     let mut ball = factory.create(ObjectType::BALL, play_area.get_width()/2 - ball_width/2, play_area.get_height()/2 - ball_width/2, ball_width, ball_width);
-    play_area.add_game_object(ball);
+    ball.vec.set_magnitude(serve_speed);
+    play_area.add_with_role(ball, Role::BALL)?;
+    Ok(())
 }
 
-fn add_goals(play_area: &mut PlayArea, factory: &mut GameObjectFactory, goal_width: usize, wall_thickness: usize) {
+fn add_goals(play_area: &mut PlayArea, factory: &mut GameObjectFactory, goal_width: usize, wall_thickness: usize) -> Result<(), PlayAreaError> {
     let left_goal = factory.create(
         ObjectType::GOAL, 
         wall_thickness, 
@@ -100,64 +379,204 @@ fn add_goals(play_area: &mut PlayArea, factory: &mut GameObjectFactory, goal_wid
         play_area.get_height() - 2*wall_thickness
     );
 
-    play_area.add_game_object(left_goal);
-    play_area.add_game_object(right_goal);
+    play_area.add_with_role(left_goal, Role::LEFT_GOAL)?;
+    play_area.add_with_role(right_goal, Role::RIGHT_GOAL)?;
+    Ok(())
 }
 
-fn init_playarea(width: usize, height: usize) -> PlayArea {
-    let mut factory = GameObjectFactory::new();
+pub(crate) fn init_playarea(width: usize, height: usize, config: &GameConfig, factory: &mut GameObjectFactory) -> Result<PlayArea, PlayAreaError> {
     let mut play_area =  PlayArea::new(width, height);
-    let wall_thickness: usize = 10;
-    let ball_width = 20;
-    let paddle_dims: (usize, usize) = (10, 50);
-    let goal_width: usize = 30;
-    let pixel_gap_between_paddle_and_wall = 30;
-    let area_dims = (width, height);
+    play_area.set_speed_limits(config.max_speed, config.speed_increment);
+    let paddle_dims: (usize, usize) = (config.paddle_width, config.paddle_height);
+    let english = English::new(config.paddle_max_deflection, config.paddle_spin);
     // walls
-    add_walls(&mut play_area, &mut factory, wall_thickness);
+    add_walls(&mut play_area, factory, config.wall_thickness)?;
     // adding the paddles
-    add_paddles(&mut play_area, &mut factory, paddle_dims, wall_thickness, pixel_gap_between_paddle_and_wall);
+    add_paddles(&mut play_area, factory, paddle_dims, config.wall_thickness, config.paddle_gap, english)?;
     // score zones
-    add_goals(&mut play_area, &mut factory, goal_width, wall_thickness);
+    add_goals(&mut play_area, factory, config.goal_width, config.wall_thickness)?;
     // adding the ball
-    add_ball(&mut play_area, &mut factory, ball_width);
-    play_area
+    add_ball(&mut play_area, factory, config.ball_width, config.serve_speed)?;
+    Ok(play_area)
 }
 
-unsafe fn init_gameview(width: usize, height: usize) -> GameView {
-    let mut gameview = GameView::sdl2(width as i32, height as i32);
+unsafe fn init_gameview(width: usize, height: usize, fullscreen: bool) -> GameView {
+    let mut gameview = if fullscreen {
+        GameView::sdl2_fullscreen(width as i32, height as i32)
+    } else {
+        GameView::sdl2(width as i32, height as i32)
+    };
     gameview.init();
     gameview
 }
 
-pub unsafe fn default(width: usize, height: usize) -> Controller {
-    Controller { 
-        play_area: init_playarea(width, height),
+pub unsafe fn default(width: usize, height: usize, config: GameConfig) -> Result<Controller, MatchError> {
+    with_options(width, height, config, MatchOptions::default(), false)
+}
+
+pub fn headless(width: usize, height: usize, config: GameConfig, seed: u64) -> Result<Controller, MatchError> {
+    let options = MatchOptions {mode: GameMode::DEMO, seed: Some(seed), ..MatchOptions::default()};
+    with_options(width, height, config, options, true)
+}
+
+// as run_headless, but with whatever drives each paddle, left then right; both are run the same way in any mode
+pub fn run_headless_with_paddles(width: usize, height: usize, config: GameConfig, seed: u64, paddles: [Box<dyn PaddleController>; 2], tick_budget: u64) -> Result<MatchResult, MatchError> {
+    let options = MatchOptions {mode: GameMode::DEMO, seed: Some(seed), ..MatchOptions::default()};
+    Ok(with_paddles(width, height, config, options, MatchSetup {seed, auto_serve: true}, paddles)?.run_headless(tick_budget))
+}
+
+fn with_options(width: usize, height: usize, config: GameConfig, mut options: MatchOptions, headless: bool) -> Result<Controller, MatchError> {
+    // every match gets a concrete seed so that it can be recorded and played back
+    let seed = options.seed
+        .or_else(|| options.playback.as_ref().map(Replay::get_seed))
+        .unwrap_or_else(rand::random);
+    // nobody is there to press serve when no one is playing, or the replay is
+    let auto_serve = headless || options.mode == GameMode::DEMO || options.playback.is_some();
+    // offset from the serve seed so the opponents don't make their mistakes in step with the serves
+    let left: Box<dyn PaddleController> = match (options.playback.take(), options.mode, options.control) {
+        (Some(replay), _, _) => Box::new(ReplayController::new(replay)),
+        (None, GameMode::DEMO, _) => Box::new(AiController::new(move || Difficulty::NORMAL.opponent(seed.wrapping_add(2)))),
+        (None, _, PaddleControl::MOUSE) => Box::new(PointerController),
+        (None, _, PaddleControl::KEYBOARD) => Box::new(KeyboardController)
+    };
+    let right: Box<dyn PaddleController> = match options.mode {
+        GameMode::VERSUS => Box::new(KeyboardController),
+        _ => {
+            let difficulty = options.difficulty;
+            Box::new(AiController::new(move || difficulty.opponent(seed.wrapping_add(1))))
+        }
+    };
+    with_paddles(width, height, config, options, MatchSetup {seed, auto_serve}, [left, right])
+}
+
+// how a match is run, as opposed to who plays it and by what rules
+#[derive(Clone, Copy)]
+struct MatchSetup {
+    seed: u64,
+    auto_serve: bool
+}
+
+fn with_paddles(width: usize, height: usize, config: GameConfig, options: MatchOptions, setup: MatchSetup, paddles: [Box<dyn PaddleController>; 2]) -> Result<Controller, MatchError> {
+    // the arena is built by subtracting from the play area's size, so it has to fit first
+    config.validate_for(width, height)?;
+    let styles = RenderStyles::from_config(&config);
+    let (input_sender, input_events) = mpsc::channel();
+    let mut factory = GameObjectFactory::new();
+    let play_area = init_playarea(width, height, &config, &mut factory)?;
+    let starting_paddles = Player::ALL.map(|player| *play_area.by_role(player.paddle()).expect("every match has both paddles"));
+    let MatchSetup {seed, auto_serve} = setup;
+    Ok(Controller { 
+        play_area,
+        starting_paddles,
+        factory,
         keep_playing: Arc::new(AtomicBool::new(true)),
         threads: Vec::new(),
         renderer_started: Arc::new(AtomicBool::new(false)),
-        keyboard_input: Arc::new(AtomicI32::new(-1)),
+        input_sender: Some(input_sender),
+        input_events,
+        input_state: InputState::new(),
         ready_to_render: Arc::new(AtomicBool::new(false)),
-        objects_to_render: Arc::new(Mutex::new(Vec::new())),
+        objects_to_render: Arc::new(Mutex::new(RenderFrame::default())),
+        clock: GameClock::new(config.tick_rate),
+        config,
         plyr_momentum: 0.0,
         comp_momentum: 0.0,
         plyr_score: 0,
-        comp_score: 0
-    }
+        comp_score: 0,
+        rng: StdRng::seed_from_u64(seed),
+        rally_ticks: 0,
+        rally_lengths: Vec::new(),
+        match_ticks: 0,
+        paddle_hits: 0,
+        mode: options.mode,
+        paddles,
+        keymap: options.keymap,
+        keymap_path: options.keymap_path,
+        rebinding: None,
+        state: GameState::TITLE,
+        receiver: Player::LEFT,
+        auto_serve,
+        fullscreen: options.fullscreen,
+        styles,
+        record_to: options.record,
+        recording: Replay::new(seed, (width, height))
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchResult {
+    pub plyr_score: u8,
+    pub comp_score: u8,
+    // length of every completed rally, in ticks from the serve until the ball entered a goal
+    pub rally_lengths: Vec<u64>,
+    // how long the match has been going, leaving out the time spent waiting on the players at the
+    // title, before a serve, paused or at the end; a replay plays back in the same number of ticks
+    pub ticks: u64
+}
+
+// snapshot of the model handed to the render thread, alpha being how far into the next tick it is
+#[derive(Default)]
+struct RenderFrame {
+    objects: Vec<GameObject>,
+    alpha: f64,
+    state: GameState,
+    // the left and right players' scores
+    score: (u8, u8),
+    prompt: Option<RebindPrompt>
+}
+
+// what the rebinding screen needs to draw: whose key and for what, and how far along it is
+#[derive(Clone, Copy)]
+struct RebindPrompt {
+    paddle: GameObject,
+    action: Action,
+    progress: (usize, usize)
 }
 
 pub struct Controller{
     play_area: PlayArea,
+    // the left and right paddles as they were set up, put back for every new match
+    starting_paddles: [GameObject; 2],
+    // makes every ball served during the match, so that each gets its own id
+    factory: GameObjectFactory,
     keep_playing: Arc<AtomicBool>,
     threads: Vec<JoinHandle<()>>,
     renderer_started: Arc<AtomicBool>,
-    keyboard_input: Arc<AtomicI32>,
+    // handed to the render thread, which forwards whatever the player does
+    input_sender: Option<Sender<InputEvent>>,
+    input_events: Receiver<InputEvent>,
+    input_state: InputState,
     ready_to_render: Arc<AtomicBool>,
-    objects_to_render: Arc<Mutex<Vec<GameObject>>>,
+    objects_to_render: Arc<Mutex<RenderFrame>>,
+    clock: GameClock,
+    config: GameConfig,
     plyr_momentum: f64,
     comp_momentum: f64,
     plyr_score: u8,
-    comp_score: u8
+    comp_score: u8,
+    rng: StdRng,
+    rally_ticks: u64,
+    rally_lengths: Vec<u64>,
+    match_ticks: u64,
+    // since the last serve, towards the next multiball
+    paddle_hits: u32,
+    mode: GameMode,
+    // what drives the left and right paddles
+    paddles: [Box<dyn PaddleController>; 2],
+    keymap: KeyMap,
+    keymap_path: Option<PathBuf>,
+    rebinding: Option<RebindSession>,
+    state: GameState,
+    // who the next serve goes to
+    receiver: Player,
+    // start matches and serve without waiting for anyone to press serve
+    auto_serve: bool,
+    fullscreen: bool,
+    // how each type of object is drawn
+    styles: RenderStyles,
+    record_to: Option<PathBuf>,
+    // every input the left paddle has received so far, one per tick
+    recording: Replay
 }
 
 impl Controller {
@@ -177,24 +596,42 @@ impl Controller {
 
         let keep_playing = Arc::clone(&self.keep_playing);
         let renderer_started = Arc::clone(&self.renderer_started);
-        let keyboard_input = Arc::clone(&self.keyboard_input);
+        let input_sender = self.input_sender.take().expect("the renderer is only started once");
+        let fullscreen = self.fullscreen;
+        let styles = self.styles;
         self.threads.push(thread::spawn(move || {
             //println!("Starting render thread");
-            let mut game_view = init_gameview(width, height);
+            let mut game_view = init_gameview(width, height, fullscreen);
             renderer_started.store(true, Ordering::Release);
             while keep_playing.load(Ordering::Acquire) {
                 while !ready_to_render.load(Ordering::Acquire) {}
                 let mut id = 0;
-                while let Some(mut obj) = objects_to_render.lock().unwrap().pop() {
-                    game_view.add_drawable_object(convert_game_object_to_drawing(id, &mut obj));
+                let mut frame = objects_to_render.lock().unwrap();
+                let alpha = frame.alpha;
+                while let Some(obj) = frame.objects.pop() {
+                    game_view.add_drawable_object(convert_game_object_to_drawing(id, &obj, alpha, &styles));
                     id += 1;
                 }
+                game_view.add_drawable_object(create_net_drawing(id, (width, height)));
+                id += 1;
+                for drawing in create_score_drawings(id, frame.score, (width, height)) {
+                    game_view.add_drawable_object(drawing);
+                }
+                id += 2;
+                // the rebinding screen takes the place of whatever state the game is in
+                let overlay = match frame.prompt {
+                    Some(prompt) => create_rebind_drawings(id, &prompt, (width, height)),
+                    None => create_state_drawings(id, frame.state, (width, height))
+                };
+                for drawing in overlay {
+                    game_view.add_drawable_object(drawing);
+                }
+                drop(frame);
                 //println!("Rendering!");
                 game_view.render();
-                if let Some(keypress) = game_view.keyboard_input() {
-                    match keypress {
-                        _ => {keyboard_input.store(keypress, Ordering::Release)}
-                    }
+                for event in game_view.input_events() {
+                    // the controller only hangs up once the game is over
+                    let _ = input_sender.send(event);
                 }
                 ready_to_render.store(false, Ordering::Release);
             }
@@ -203,136 +640,745 @@ impl Controller {
         }));
     }
 
-    fn resolve_model(&mut self) {
-        let mut i = 0;
-        while self.ready_to_render.load(Ordering::Acquire) {
-            i += 1;
+    // how far between the last two ticks to draw; nothing moves while the model is frozen, so it's drawn where it stopped
+    fn frame_alpha(&self) -> f64 {
+        if self.state.is_live() && self.rebinding.is_none() {
+            self.clock.alpha()
+        } else {
+            1.0
         }
-        for object in self.play_area.game_objects.iter() {
-            self.objects_to_render.lock().unwrap().push(object.clone())
+    }
+
+    // hands the current model to the render thread, unless it is still busy with the previous frame
+    fn submit_frame(&mut self, alpha: f64) {
+        if self.ready_to_render.load(Ordering::Acquire) {
+            return
         }
+        let mut frame = self.objects_to_render.lock().unwrap();
+        frame.objects.clear();
+        frame.objects.extend(self.play_area.objects().iter().copied());
+        frame.alpha = alpha;
+        frame.state = self.state;
+        frame.score = (self.plyr_score, self.comp_score);
+        frame.prompt = self.rebind_prompt();
+        drop(frame);
         self.ready_to_render.store(true, Ordering::Release);
-        self.play_area.resolve_object_behaviors()
+    }
+
+    fn resolve_model(&mut self) {
+        let contacts = self.play_area.resolve_object_behaviors();
+        self.count_paddle_hits(&contacts);
     }
 }
 
 impl Controller {
-    const MOMENTUM_UPPER: f64 = 2.5;
-    const MOMENTUM_LOWER: f64 = -2.5;
-    const INCREMENT: f64 = 0.5;
+    pub const MOMENTUM_UPPER: f64 = 2.5;
+    pub const MOMENTUM_LOWER: f64 = -2.5;
+    pub const INCREMENT: f64 = 0.5;
+    pub const DECAY: f64 = 0.1;
     const RESTING: f64 = 0.0;
 
-    fn move_up(paddle: &mut GameObject, momentum: &mut f64) {
-        if *momentum > Self::MOMENTUM_LOWER {
-            *momentum -= Self::INCREMENT;
+    // speed_factor scales the momentum caps, letting computer paddles be slower or faster than a human
+    fn move_up(config: &GameConfig, paddle: &mut GameObject, momentum: &mut f64, speed_factor: f64) {
+        if *momentum > config.momentum_lower * speed_factor {
+            *momentum -= config.momentum_increment;
         }
         Self::resolve_move(paddle, momentum)
     }
 
-    fn move_down(paddle: &mut GameObject, momentum: &mut f64) {
-        if *momentum < Self::MOMENTUM_UPPER {
-            *momentum += Self::INCREMENT;
+    fn move_down(config: &GameConfig, paddle: &mut GameObject, momentum: &mut f64, speed_factor: f64) {
+        if *momentum < config.momentum_upper * speed_factor {
+            *momentum += config.momentum_increment;
         }
         Self::resolve_move(paddle, momentum);
     }
 
-    fn decay(momentum: &mut f64) {
+    fn decay(config: &GameConfig, momentum: &mut f64) {
         if *momentum > Self::RESTING {
-            *momentum -= 0.1;
+            *momentum -= config.momentum_decay;
         } else if *momentum < Self::RESTING {
-            *momentum += 0.1;
+            *momentum += config.momentum_decay;
+        }
+    }
+
+    fn apply_input(config: &GameConfig, paddle: &mut GameObject, momentum: &mut f64, input: PaddleInput) {
+        match input {
+            PaddleInput::UP => Self::move_up(config, paddle, momentum, Self::HUMAN_SPEED),
+            PaddleInput::DOWN => Self::move_down(config, paddle, momentum, Self::HUMAN_SPEED),
+            PaddleInput::IDLE => {
+                Self::decay(config, momentum);
+                Self::resolve_move(paddle, momentum)
+            },
+            PaddleInput::STICK(tilt) => Self::steer(config, paddle, momentum, tilt as f64 / 100.0)
         }
     }
 
-    fn momentum_decay(&mut self) {
-        Self::decay(&mut self.plyr_momentum);
-        Self::resolve_move(&mut self.play_area.game_objects[4], &mut self.plyr_momentum)
+    // the stick's tilt sets how fast the paddle may go, the momentum model how quickly it gets there
+    fn steer(config: &GameConfig, paddle: &mut GameObject, momentum: &mut f64, tilt: f64) {
+        let speed_factor = tilt.abs() * Self::HUMAN_SPEED;
+        if tilt < 0.0 && *momentum > config.momentum_lower * speed_factor {
+            Self::move_up(config, paddle, momentum, speed_factor)
+        } else if tilt > 0.0 && *momentum < config.momentum_upper * speed_factor {
+            Self::move_down(config, paddle, momentum, speed_factor)
+        } else {
+            Self::decay(config, momentum);
+            Self::resolve_move(paddle, momentum)
+        }
     }
 
     fn resolve_move(paddle: &mut GameObject, momentum: &mut f64) {
-        paddle.pos.inc_y_pos(*momentum)
+        let mut next_pos = paddle.pos;
+        next_pos.inc_y_pos(*momentum);
+        paddle.update_pos(next_pos);
+        // published on the paddle so the model can put spin on the ball
+        paddle.vec = EuclideanVector::from_components(0.0, *momentum)
     }
 }
 
 impl Controller {
-    unsafe fn handle_input(&mut self) {
-        match self.keyboard_input.load(Ordering::Acquire) {
-            27 => {self.keep_playing.store(false, Ordering::Release)},
-            119 => Self::move_up(&mut self.play_area.game_objects[4], &mut self.plyr_momentum),
-            115 => Self::move_down(&mut self.play_area.game_objects[4], &mut self.plyr_momentum),
-            _ => self.momentum_decay()
+    const HUMAN_SPEED: f64 = 1.0;
+
+    // folds the events forwarded by the render thread into the input state
+    fn drain_input_events(&mut self) {
+        self.input_state.begin_tick();
+        while let Ok(event) = self.input_events.try_recv() {
+            self.input_state.apply(event);
+        }
+        if self.input_state.quit_requested() {
+            self.keep_playing.store(false, Ordering::Release);
         }
     }
+
+    // asks whatever drives each paddle what it wants to do, and does it
+    // false once either controller has run out, and with it the match
+    fn drive_paddles(&mut self) -> bool {
+        let mut driven = true;
+        for (player, driver) in Player::ALL.into_iter().zip(self.paddles.iter_mut()) {
+            let snapshot = Snapshot {play_area: &self.play_area, input: &self.input_state, keymap: &self.keymap, player};
+            let intent = driver.intent(&snapshot).unwrap_or_else(|| {
+                self.keep_playing.store(false, Ordering::Release);
+                driven = false;
+                Intent::INPUT(PaddleInput::IDLE)
+            });
+            let momentum = match player {
+                Player::LEFT => &mut self.plyr_momentum,
+                Player::RIGHT => &mut self.comp_momentum
+            };
+            if let (Player::LEFT, Intent::INPUT(input)) = (player, intent) {
+                self.recording.push(input);
+            }
+            let paddle = self.play_area.by_role_mut(player.paddle()).expect("every match has both paddles");
+            Self::apply_intent(&self.config, paddle, momentum, intent);
+        }
+        driven
+    }
 }
 
 impl Controller {
-    fn resolve_computer_turn(&mut self) {
-        let (ball_y_origin, ball_y_extent) = (
-            self.play_area.game_objects[8].pos.get_y_pos(), 
-            self.play_area.game_objects[8].y_extent()
-        );
-
-        let ball_y_center = ball_y_origin + (ball_y_extent - ball_y_origin).abs();
+    fn start_rebinding(&mut self) {
+        let players: &[Player] = if self.mode == GameMode::VERSUS {&Player::ALL} else {&[Player::LEFT]};
+        self.rebinding = Some(RebindSession::new(players, &self.keymap));
+    }
 
-        let paddle = &mut self.play_area.game_objects[5];
-        let paddle_y_center = paddle.pos.get_y_pos() + (paddle.y_extent() - paddle.pos.get_y_pos()).abs();
+    // binds the first key pressed this tick to the slot being asked for
+    fn resolve_rebinding(&mut self) {
+        let Some(session) = self.rebinding.as_mut() else {return};
+        let Some(key) = self.input_state.just_pressed().next().map(Key) else {return};
+        let keep_going = session.press(key, &mut self.keymap);
+        if !keep_going {
+            self.rebinding = None;
+        } else if session.is_done() {
+            self.rebinding = None;
+            self.finish_rebinding();
+        }
+    }
 
-        if paddle_y_center > ball_y_center {
-            Self::move_up(paddle, &mut self.comp_momentum)
-        } else if paddle_y_center < ball_y_center {
-            Self::move_down(paddle, &mut self.comp_momentum)
+    fn finish_rebinding(&mut self) {
+        if let Some(path) = &self.keymap_path {
+            if let Err(e) = self.keymap.save(path) {
+                eprintln!("{}: {e}", path.display());
+            }
         }
+    }
 
+    fn rebind_prompt(&self) -> Option<RebindPrompt> {
+        let session = self.rebinding.as_ref()?;
+        let (player, action) = session.current()?;
+        let paddle = *self.play_area.by_role(player.paddle())?;
+        Some(RebindPrompt {paddle, action, progress: session.progress()})
+    }
+}
 
+impl Controller {
+    // keep this far from the target before setting off, so the paddle doesn't shiver around it
+    const DEADBAND: f64 = 2.0;
+
+    // heads for where a computer player wants the middle of the paddle to be
+    fn move_toward(config: &GameConfig, paddle: &mut GameObject, momentum: &mut f64, target: f64, speed_factor: f64) {
+        let paddle_y_center = paddle.midpoint().get_y_pos();
+        if paddle_y_center > target + Self::DEADBAND {
+            Self::move_up(config, paddle, momentum, speed_factor)
+        } else if paddle_y_center < target - Self::DEADBAND {
+            Self::move_down(config, paddle, momentum, speed_factor)
+        } else {
+            Self::decay(config, momentum);
+            Self::resolve_move(paddle, momentum)
+        }
+    }
 
+    fn apply_intent(config: &GameConfig, paddle: &mut GameObject, momentum: &mut f64, intent: Intent) {
+        match intent {
+            Intent::INPUT(input) => Self::apply_input(config, paddle, momentum, input),
+            Intent::TOWARD(target, speed_factor) => Self::move_toward(config, paddle, momentum, target, speed_factor)
+        }
     }
 }
 
 impl Controller {
-    const WIN_SCORE: u8 = 3;
+    pub const WIN_SCORE: u8 = 3;
+    pub const SERVE_SPEED: f64 = 1.0;
 
+    // the paddles settle and a fresh ball waits in the middle in place of any still in play
     fn reset(&mut self) {
-        println!("{} | {}", self.plyr_score, self.comp_score);
+        let balls: Vec<ObjectHandle> = self.play_area.with_handles()
+            .filter(|(_, obj)| obj.object_type == ObjectType::BALL)
+            .map(|(handle, _)| handle)
+            .collect();
+        for ball in balls {
+            self.play_area.despawn(ball).expect("the ball is in play");
+        }
         self.plyr_momentum = 0.0;
         self.comp_momentum = 0.0;
-        let w_h = self.play_area.dims_as_tuple();
-        let ball = &mut self.play_area.game_objects[8];
-        let ball_width = ball.dim.get_width();
-        ball.pos = Position::new(w_h.0/2 - (ball_width/2.0) as usize, w_h.1/2 - (ball_width/2.0) as usize);
-        ball.vec = EuclideanVector::new(1.0, if rand::random() {-135.0} else {45.0})
+        self.rally_ticks = 0;
+        self.paddle_hits = 0;
+        self.spawn_ball(None);
+    }
+
+    // a new ball in the middle of the play area, joining at the end of the tick: served towards a player, or waiting to be
+    fn spawn_ball(&mut self, toward: Option<Player>) {
+        let (width, height) = self.play_area.dims_as_tuple();
+        let ball_width = self.config.ball_width;
+        let mut ball = self.factory.create(ObjectType::BALL, width/2 - ball_width/2, height/2 - ball_width/2, ball_width, ball_width);
+        ball.vec = match toward {
+            Some(player) => self.serve_vector(player),
+            None => EuclideanVector::new(0.0, 0.0)
+        };
+        self.play_area.spawn_with_role(ball, Role::BALL).expect("the middle of the play area is in bounds");
+    }
+
+    /*
+        Serving.
+            The ball leaves straight across towards the receiver, turned by a random angle of up to
+            serve_cone degrees either way. The angle comes from the match's seeded generator, so a
+            match served from the same seed is served the same way.
+     */
+    fn serve_vector(&mut self, toward: Player) -> EuclideanVector {
+        let cone = self.config.serve_cone;
+        let turn = if cone > 0.0 {self.rng.gen_range(-cone..=cone)} else {0.0};
+        let across = match toward {
+            Player::LEFT => 180.0,
+            Player::RIGHT => 0.0
+        };
+        EuclideanVector::new(self.config.serve_speed, across + turn)
+    }
+
+    // sends the waiting ball on its way
+    fn launch(&mut self, toward: Player) {
+        let vec = self.serve_vector(toward);
+        if let Some(ball) = self.play_area.by_role_mut(Role::BALL) {
+            ball.vec = vec;
+        }
+    }
+
+    // who the next serve goes to, once scorer has won a point
+    fn next_receiver(&self, scorer: Player) -> Player {
+        match self.config.serve_to {
+            ServeRule::LOSER => scorer.opponent(),
+            ServeRule::ALTERNATE => self.receiver.opponent()
+        }
+    }
+
+    fn serving(&self) -> GameState {
+        let countdown = (self.config.serve_countdown * self.config.tick_rate).round() as u32;
+        GameState::SERVING(self.receiver, if countdown > 0 {Some(countdown)} else {None})
     }
 
+    /*
+        Multiball.
+            Every multiball_hits paddle hits, counted across all the balls since the last serve,
+            another ball is served from the middle, as long as fewer than max_balls are in play.
+     */
+    fn count_paddle_hits(&mut self, contacts: &[(ObjectHandle, ObjectHandle)]) {
+        let hits = contacts.iter()
+            .filter(|(_, other)| self.play_area.get(*other).is_some_and(|obj| obj.object_type == ObjectType::PADDLE))
+            .count() as u32;
+        let every = self.config.multiball_hits;
+        for _ in 0..hits {
+            self.paddle_hits += 1;
+            // no remainder to be had when multiball is off, with every at 0
+            if self.paddle_hits.checked_rem(every) == Some(0) && self.play_area.pending_count(ObjectType::BALL) < self.config.max_balls {
+                let toward = if self.rng.gen() {Player::LEFT} else {Player::RIGHT};
+                self.spawn_ball(Some(toward));
+            }
+        }
+    }
+
+    // any ball in a goal scores a point and leaves the play area; the others play on
     fn check_if_score(&mut self) {
-        let left_score_zone = self.play_area.game_objects[6];
-        let right_score_zone = self.play_area.game_objects[7];
-        let ball = &mut self.play_area.game_objects[8];
-        if ball.intersecting(&left_score_zone) {
-            self.comp_score += 1;
-            self.reset();
-        } else if ball.intersecting(&right_score_zone) {
-            self.plyr_score += 1;
+        let (Some(left_score_zone), Some(right_score_zone)) = (
+            self.play_area.by_role(Role::LEFT_GOAL),
+            self.play_area.by_role(Role::RIGHT_GOAL)
+        ) else {return};
+        let scored: Vec<(ObjectHandle, Player)> = self.play_area.with_handles()
+            .filter(|(_, obj)| obj.object_type == ObjectType::BALL)
+            .filter_map(|(handle, ball)| {
+                if ball.intersecting(left_score_zone) {
+                    Some((handle, Player::RIGHT))
+                } else if ball.intersecting(right_score_zone) {
+                    Some((handle, Player::LEFT))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        // with several balls in play both sides can score on one tick; the last goal counted decides the serve
+        let Some(&(_, last_scorer)) = scored.last() else {return};
+        for (ball, scorer) in scored {
+            match scorer {
+                Player::LEFT => self.plyr_score += 1,
+                Player::RIGHT => self.comp_score += 1
+            }
+            self.rally_lengths.push(self.rally_ticks);
+            self.play_area.despawn(ball).expect("a ball in a goal is in play");
+        }
+        if let Some(winner) = self.winner() {
+            self.enter(GameState::GAME_OVER(winner));
+        } else if self.play_area.pending_count(ObjectType::BALL) == 0 {
             self.reset();
+            self.receiver = self.next_receiver(last_scorer);
+            self.enter(GameState::POINT_SCORED(last_scorer, self.point_scored_ticks()));
         }
     }
 
-    fn check_win_condition(&mut self) {
-        if self.plyr_score == Self::WIN_SCORE || self.comp_score == Self::WIN_SCORE {
-            self.keep_playing.store(false, Ordering::Release);
-        } 
+    fn winner(&self) -> Option<Player> {
+        if self.plyr_score >= self.config.win_score {
+            Some(Player::LEFT)
+        } else if self.comp_score >= self.config.win_score {
+            Some(Player::RIGHT)
+        } else {
+            None
+        }
+    }
+
+    fn point_scored_ticks(&self) -> u32 {
+        (GameState::POINT_SCORED_DELAY.as_secs_f64() * self.config.tick_rate).round() as u32
+    }
+}
+
+impl Controller {
+    fn follow(&mut self, transition: Transition) {
+        match transition {
+            Transition::TO(state) => self.enter(state),
+            Transition::NEW_MATCH => {
+                // every match starts as the first did, so that a recording of any of them plays back
+                // from the replay's seed alone
+                let seed = self.recording.get_seed();
+                self.recording = Replay::new(seed, self.play_area.dims_as_tuple());
+                self.rng = StdRng::seed_from_u64(seed);
+                for (player, paddle) in Player::ALL.into_iter().zip(self.starting_paddles) {
+                    if let Some(current) = self.play_area.by_role_mut(player.paddle()) {
+                        *current = paddle;
+                    }
+                }
+                for driver in self.paddles.iter_mut() {
+                    driver.new_match();
+                }
+                self.plyr_score = 0;
+                self.comp_score = 0;
+                self.rally_lengths.clear();
+                self.match_ticks = 0;
+                self.reset();
+                // the opening ball has to be there before the first serve, which may be this tick
+                self.play_area.apply_pending();
+                self.receiver = if self.rng.gen() {Player::LEFT} else {Player::RIGHT};
+                self.enter(self.serving());
+            },
+            Transition::EXIT => self.keep_playing.store(false, Ordering::Release)
+        }
+    }
+
+    fn enter(&mut self, state: GameState) {
+        let previous = std::mem::replace(&mut self.state, state);
+        if let (GameState::SERVING(toward, _), GameState::PLAYING) = (previous, state) {
+            self.launch(toward);
+        }
+        match state {
+            GameState::SERVING(..) if self.auto_serve => self.enter(GameState::PLAYING),
+            GameState::GAME_OVER(_) if self.auto_serve => self.follow(Transition::EXIT),
+            _ => ()
+        }
+    }
+
+    // the first of quit, pause and serve pressed this tick that means something in the current state
+    fn handle_actions(&mut self) {
+        let transition = [Action::QUIT, Action::PAUSE, Action::SERVE].into_iter()
+            .filter(|action| self.keymap.anyone_pressed(*action, &self.input_state))
+            .find_map(|action| self.state.on_action(action));
+        if let Some(transition) = transition {
+            self.follow(transition);
+        }
+    }
+
+    // the paddles move first, so what is pressed on a tick already counts on that tick
+    fn update_playing(&mut self) {
+        if !self.drive_paddles() {
+            return
+        }
+        self.match_ticks += 1;
+        self.rally_ticks += 1;
+        self.resolve_model();
+        self.check_if_score();
+    }
+
+    fn update_point_scored(&mut self, scorer: Player, ticks_left: u32) {
+        self.match_ticks += 1;
+        if ticks_left > 1 {
+            self.state = GameState::POINT_SCORED(scorer, ticks_left - 1);
+        } else {
+            self.enter(self.serving());
+        }
+    }
+
+    fn update_serving(&mut self, toward: Player, countdown: Option<u32>) {
+        match countdown {
+            Some(ticks_left) if ticks_left > 1 => self.state = GameState::SERVING(toward, Some(ticks_left - 1)),
+            Some(_) => self.enter(GameState::PLAYING),
+            None => ()
+        }
     }
 }
 
 impl Controller {
-    unsafe fn run(&mut self) {
+    // longest the main loop sleeps between frames, so the renderer keeps receiving fresh alphas
+    const MAX_IDLE: Duration = Duration::from_millis(1);
+
+    // advances the game by exactly one fixed timestep; only a match being played moves the model
+    fn tick(&mut self) {
+        self.drain_input_events();
+        if self.rebinding.is_some() {
+            self.resolve_rebinding();
+            return
+        }
+        if self.keymap.anyone_pressed(Action::REBIND, &self.input_state) && self.mode != GameMode::DEMO {
+            self.start_rebinding();
+            return
+        }
+        self.handle_actions();
+        if self.auto_serve && self.state == GameState::TITLE {
+            self.follow(Transition::NEW_MATCH);
+        }
+        match self.state {
+            state if state.is_live() => self.update_playing(),
+            GameState::POINT_SCORED(scorer, ticks_left) => self.update_point_scored(scorer, ticks_left),
+            GameState::SERVING(toward, countdown) => self.update_serving(toward, countdown),
+            _ => ()
+        }
+        // whatever was spawned or despawned during the tick comes and goes before the next one
+        self.play_area.apply_pending();
+    }
+
+    unsafe fn run(&mut self) -> Result<(), ReplayError> {
         self.render();
+        while !self.renderer_started.load(Ordering::Acquire) {
+            thread::yield_now();
+        }
+        self.clock.restart();
         while self.keep_playing.load(Ordering::Acquire) {
-            self.handle_input();
-            self.resolve_computer_turn();
-            self.resolve_model();
-            self.check_if_score();
-            self.check_win_condition();
+            for _ in 0..self.clock.advance() {
+                self.tick();
+                if !self.keep_playing.load(Ordering::Acquire) {
+                    break;
+                }
+            }
+            self.submit_frame(self.frame_alpha());
+            thread::sleep(self.clock.time_until_next_tick().min(Self::MAX_IDLE));
         }
         println!("Waiting on threads...");
         self.wait_on_all_threads();
+        match &self.record_to {
+            Some(path) => self.recording.save(path),
+            None => Ok(())
+        }
     } 
-}
\ No newline at end of file
+}
+
+impl Controller {
+    fn run_headless(&mut self, tick_budget: u64) -> MatchResult {
+        let mut ticks = 0;
+        while self.keep_playing.load(Ordering::Acquire) && ticks < tick_budget {
+            self.tick();
+            ticks += 1;
+        }
+        self.result()
+    }
+
+    fn result(&self) -> MatchResult {
+        MatchResult {
+            plyr_score: self.plyr_score,
+            comp_score: self.comp_score,
+            rally_lengths: self.rally_lengths.clone(),
+            ticks: self.match_ticks
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{controller::{config::GameConfig, input::PaddleInput, options::{MatchOptions, PaddleControl, Difficulty}, paddle_controller::{AiController, ReplayController}, replay::Replay}, view::input::InputEvent};
+
+    use crate::{controller::{config::ServeRule, keymap::{Key, Player}, state::GameState}, model::pong::{Role, ObjectHandle, game_objects::objects::ObjectType}};
+
+    use super::{headless, run_headless, run_headless_with, run_headless_with_paddles, with_options, MatchError};
+
+    #[test]
+    fn headless_respects_tick_budget() {
+        let result = run_headless(1280, 600, GameConfig::default(), 500, 7).unwrap();
+        assert!(result.ticks <= 500);
+    }
+
+    #[test]
+    fn arenas_too_big_for_the_play_area_are_refused() {
+        let config = GameConfig {wall_thickness: 400, ..GameConfig::default()};
+        assert!(matches!(run_headless(640, 480, config, 10, 1), Err(MatchError::Config(_))));
+        assert!(matches!(headless(20, 20, GameConfig::default(), 1), Err(MatchError::Config(_))));
+    }
+
+    #[test]
+    fn headless_is_reproducible_from_seed() {
+        assert_eq!(
+            run_headless(1280, 600, GameConfig::default(), 20_000, 42).unwrap(),
+            run_headless(1280, 600, GameConfig::default(), 20_000, 42).unwrap()
+        );
+    }
+
+    #[test]
+    fn headless_records_a_rally_per_point() {
+        let result = run_headless(1280, 600, GameConfig::default(), 200_000, 3).unwrap();
+        assert_eq!(result.rally_lengths.len(), (result.plyr_score + result.comp_score) as usize);
+    }
+
+    #[test]
+    fn headless_plays_to_configured_win_score() {
+        let config = GameConfig {win_score: 1, ..GameConfig::default()};
+        let result = run_headless(1280, 600, config, 200_000, 3).unwrap();
+        assert_eq!(result.plyr_score.max(result.comp_score), 1);
+    }
+
+    #[test]
+    fn playback_ends_with_the_replay() {
+        let mut replay = Replay::new(11, (1280, 600));
+        for _ in 0..300 {
+            replay.push(PaddleInput::UP);
+        }
+        let options = MatchOptions {playback: Some(replay), ..MatchOptions::default()};
+        let result = run_headless_with(1280, 600, GameConfig::default(), options, 10_000).unwrap();
+        assert_eq!(result.ticks, 300);
+    }
+
+    #[test]
+    fn mouse_paddle_chases_pointer_within_momentum_caps() {
+        let config = GameConfig::default();
+        let options = MatchOptions {control: PaddleControl::MOUSE, seed: Some(5), ..MatchOptions::default()};
+        let mut controller = with_options(1280, 600, config.clone(), options, true).unwrap();
+        controller.input_sender.as_ref().unwrap().send(InputEvent::MOUSEMOTION(0, 450)).unwrap();
+        for _ in 0..600 {
+            let before = controller.play_area.by_role(Role::LEFT_PADDLE).unwrap().pos.get_y_pos();
+            controller.tick();
+            let step = controller.play_area.by_role(Role::LEFT_PADDLE).unwrap().pos.get_y_pos() - before;
+            assert!(step <= config.momentum_upper + config.momentum_increment + 1e-9);
+        }
+        assert!((controller.play_area.by_role(Role::LEFT_PADDLE).unwrap().midpoint().get_y_pos() - 450.0).abs() < 2.0);
+    }
+
+    #[test]
+    fn either_side_takes_any_controller() {
+        // the computer on the left, a script on the right that never moves
+        let mut script = Replay::new(4, (1280, 600));
+        for _ in 0..50_000 {
+            script.push(PaddleInput::IDLE);
+        }
+        let paddles = [
+            Box::new(AiController::new(|| Difficulty::HARD.opponent(1))) as _,
+            Box::new(ReplayController::new(script)) as _
+        ];
+        let result = run_headless_with_paddles(1280, 600, GameConfig::default(), 4, paddles, 50_000).unwrap();
+        assert_eq!(result.plyr_score, GameConfig::default().win_score);
+    }
+
+    #[test]
+    fn multiball_serves_extra_balls_and_scores_each() {
+        let config = GameConfig {max_balls: 3, multiball_hits: 2, win_score: 5, ..GameConfig::default()};
+        let mut controller = headless(1280, 600, config, 8).unwrap();
+        let mut most_balls = 0;
+        for _ in 0..200_000 {
+            if !controller.keep_playing.load(std::sync::atomic::Ordering::Acquire) {
+                break
+            }
+            controller.tick();
+            let balls = controller.play_area.objects().iter().filter(|obj| obj.object_type == ObjectType::BALL).count();
+            assert!((1..=3).contains(&balls));
+            most_balls = most_balls.max(balls);
+        }
+        assert_eq!(most_balls, 3);
+        assert_eq!(controller.rally_lengths.len(), (controller.plyr_score + controller.comp_score) as usize);
+    }
+
+    #[test]
+    fn keys_move_the_match_through_its_states() {
+        let options = MatchOptions {seed: Some(2), ..MatchOptions::default()};
+        let mut controller = with_options(1280, 600, GameConfig::default(), options, true).unwrap();
+        // as if someone were at the keyboard
+        controller.auto_serve = false;
+        let press = |controller: &mut super::Controller, key: Key| {
+            for event in [InputEvent::KEYDOWN(key.0), InputEvent::KEYUP(key.0)] {
+                controller.input_sender.as_ref().unwrap().send(event).unwrap();
+                controller.tick();
+            }
+        };
+        let ball_x = |controller: &super::Controller| controller.play_area.by_role(Role::BALL).unwrap().pos.get_x_pos();
+
+        assert_eq!(controller.state, GameState::TITLE);
+        press(&mut controller, Key::from_name("space").unwrap());
+        assert!(matches!(controller.state, GameState::SERVING(_, None)));
+        let waiting = ball_x(&controller);
+        press(&mut controller, Key::from_name("p").unwrap());
+        assert!(matches!(controller.state, GameState::SERVING(..)));
+        assert_eq!(ball_x(&controller), waiting);
+
+        press(&mut controller, Key::from_name("space").unwrap());
+        assert_eq!(controller.state, GameState::PLAYING);
+        press(&mut controller, Key::ESCAPE);
+        assert_eq!(controller.state, GameState::PAUSED);
+        let paused = ball_x(&controller);
+        assert_ne!(paused, waiting);
+        for _ in 0..10 {
+            controller.tick();
+        }
+        assert_eq!(ball_x(&controller), paused);
+
+        press(&mut controller, Key::ESCAPE);
+        assert!(!controller.keep_playing.load(std::sync::atomic::Ordering::Acquire));
+    }
+
+    #[test]
+    fn restarted_matches_play_back_from_their_recording() {
+        let config = GameConfig {win_score: 2, paddle_height: 200, ..GameConfig::default()};
+        let mut controller = with_options(1280, 600, config.clone(), MatchOptions {seed: Some(9), ..MatchOptions::default()}, true).unwrap();
+        controller.auto_serve = false;
+        let send = |controller: &mut super::Controller, event: InputEvent| {
+            controller.input_sender.as_ref().unwrap().send(event).unwrap();
+            controller.tick();
+        };
+        let press = |controller: &mut super::Controller, key: Key| {
+            send(controller, InputEvent::KEYDOWN(key.0));
+            send(controller, InputEvent::KEYUP(key.0));
+        };
+        // plays to the end, nudging the paddle with key now and then and serving whenever the ball is waiting
+        let play_out = |controller: &mut super::Controller, key: &str| {
+            let (space, key) = (Key::from_name("space").unwrap(), Key::from_name(key).unwrap());
+            for i in 0..200_000 {
+                match controller.state {
+                    GameState::GAME_OVER(_) => return,
+                    GameState::SERVING(..) => press(controller, space),
+                    _ if i % 600 == 0 => send(controller, InputEvent::KEYDOWN(key.0)),
+                    _ if i % 600 == 40 => send(controller, InputEvent::KEYUP(key.0)),
+                    _ => controller.tick()
+                }
+            }
+            panic!("the match never ended");
+        };
+        let space = Key::from_name("space").unwrap();
+
+        // backing out of the first serve to the title, then a match and a rematch
+        press(&mut controller, space);
+        press(&mut controller, Key::ESCAPE);
+        assert_eq!(controller.state, GameState::TITLE);
+        press(&mut controller, space);
+        play_out(&mut controller, "s");
+        let first = controller.result();
+        press(&mut controller, space);
+        play_out(&mut controller, "w");
+        let rematch = controller.result();
+
+        let options = MatchOptions {playback: Some(controller.recording.clone()), ..MatchOptions::default()};
+        assert_eq!(run_headless_with(1280, 600, config, options, 1_000_000).unwrap(), rematch);
+        // steered the other way, so that playing back the first match instead would be caught
+        assert_ne!(first, rematch);
+    }
+
+    #[test]
+    fn frozen_models_are_drawn_where_they_stopped() {
+        let mut controller = with_options(1280, 600, GameConfig::default(), MatchOptions::default(), true).unwrap();
+        controller.state = GameState::PAUSED;
+        assert_eq!(controller.frame_alpha(), 1.0);
+        controller.state = GameState::SERVING(Player::LEFT, None);
+        assert_eq!(controller.frame_alpha(), 1.0);
+    }
+
+    #[test]
+    fn goals_on_both_sides_in_one_tick_serve_by_the_last() {
+        let config = GameConfig {max_balls: 2, serve_to: ServeRule::LOSER, ..GameConfig::default()};
+        let mut controller = with_options(1280, 600, config.clone(), MatchOptions {seed: Some(5), ..MatchOptions::default()}, true).unwrap();
+        let balls: Vec<ObjectHandle> = controller.play_area.with_handles()
+            .filter(|(_, obj)| obj.object_type == ObjectType::BALL)
+            .map(|(handle, _)| handle)
+            .collect();
+        for ball in balls {
+            controller.play_area.despawn(ball).unwrap();
+        }
+        controller.play_area.apply_pending();
+        // into the right goal first, so the left player's goal is counted before the right player's
+        for goal in [Role::RIGHT_GOAL, Role::LEFT_GOAL] {
+            let pos = controller.play_area.by_role(goal).unwrap().pos;
+            let ball = controller.factory.create(ObjectType::BALL, pos.get_x_pos() as usize, pos.get_y_pos() as usize + 100, config.ball_width, config.ball_width);
+            controller.play_area.spawn(ball).unwrap();
+        }
+        controller.play_area.apply_pending();
+        controller.state = GameState::PLAYING;
+
+        controller.check_if_score();
+        assert_eq!((controller.plyr_score, controller.comp_score), (1, 1));
+        assert!(matches!(controller.state, GameState::POINT_SCORED(Player::RIGHT, _)));
+        assert_eq!(controller.receiver, Player::LEFT);
+    }
+
+    #[test]
+    fn serve_counts_down_and_leaves_within_the_cone() {
+        let config = GameConfig {serve_countdown: 0.5, serve_cone: 20.0, serve_to: ServeRule::ALTERNATE, ..GameConfig::default()};
+        let mut controller = with_options(1280, 600, config.clone(), MatchOptions {seed: Some(6), ..MatchOptions::default()}, true).unwrap();
+        controller.auto_serve = false;
+        let space = Key::from_name("space").unwrap();
+        controller.input_sender.as_ref().unwrap().send(InputEvent::KEYDOWN(space.0)).unwrap();
+        controller.tick();
+        let GameState::SERVING(toward, Some(countdown)) = controller.state else {panic!("not serving: {}", controller.state)};
+        // the tick serve was pressed on counts
+        assert_eq!(countdown + 1, (0.5 * config.tick_rate) as u32);
+
+        for _ in 0..countdown {
+            assert!(matches!(controller.state, GameState::SERVING(..)));
+            controller.tick();
+        }
+        assert_eq!(controller.state, GameState::PLAYING);
+        let vec = controller.play_area.by_role(Role::BALL).unwrap().vec;
+        let across = match toward {
+            Player::LEFT => -1.0,
+            Player::RIGHT => 1.0
+        };
+        assert!(vec.x_component() * across > 0.0);
+        assert!((vec.y_component() / vec.x_component()).abs() <= 20.0_f64.to_radians().tan() + 1e-9);
+
+        assert_eq!(controller.next_receiver(Player::LEFT), toward.opponent());
+        controller.config.serve_to = ServeRule::LOSER;
+        assert_eq!(controller.next_receiver(Player::LEFT), Player::RIGHT);
+    }
+}
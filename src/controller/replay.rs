@@ -0,0 +1,161 @@
+use std::{fmt::Display, fs, io, path::Path};
+
+use crate::controller::input::PaddleInput;
+
+/*
+    Replays.
+        The simulation is deterministic given the seed, the play area and the inputs, so a replay
+        only stores those: a header, then the human paddle's input for every tick, run-length
+        encoded as whitespace separated tokens (U for up, D for down, . for idle, and S followed by
+        the tilt in percent and a colon for an analog stick). For example
+
+            rust-pong replay 1
+            seed 42
+            area 1280 600
+            .120 U14 .3 D9 S-55:20
+
+        Replays are only faithful when played back with the same match rules they were recorded with.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay {
+    seed: u64,
+    area: (usize, usize),
+    inputs: Vec<PaddleInput>
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    Format(String)
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Io(e)          => write!(f, "could not access replay file: {e}"),
+            ReplayError::Format(why)    => write!(f, "malformed replay file: {why}"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(e: io::Error) -> Self {
+        ReplayError::Io(e)
+    }
+}
+
+impl Replay {
+    const HEADER: &'static str = "rust-pong replay 1";
+    // a little over a day at the default tick rate; anything longer is a corrupt or hostile file
+    pub const MAX_TICKS: usize = 10_000_000;
+
+    pub fn new(seed: u64, area: (usize, usize)) -> Self {
+        Self {seed, area, inputs: Vec::new()}
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_area(&self) -> (usize, usize) {
+        self.area
+    }
+
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    pub fn push(&mut self, input: PaddleInput) {
+        self.inputs.push(input)
+    }
+
+    // input for the given tick, or None once the recording has run out
+    pub fn input_at(&self, tick: usize) -> Option<PaddleInput> {
+        self.inputs.get(tick).copied()
+    }
+}
+
+impl Replay {
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        Ok(fs::write(path, self.to_text())?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        Self::from_text(&fs::read_to_string(path)?)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut runs: Vec<String> = Vec::new();
+        let mut i = 0;
+        while i < self.inputs.len() {
+            let input = self.inputs[i];
+            let run = self.inputs[i..].iter().take_while(|other| **other == input).count();
+            runs.push(format!("{}{}", input.symbol(), run));
+            i += run;
+        }
+        format!("{}\nseed {}\narea {} {}\n{}\n", Self::HEADER, self.seed, self.area.0, self.area.1, runs.join(" "))
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, ReplayError> {
+        let format_error = |why: &str| ReplayError::Format(why.to_string());
+        let mut lines = text.lines();
+        if lines.next() != Some(Self::HEADER) {
+            return Err(format_error("missing replay header"))
+        }
+        let seed = lines.next()
+            .and_then(|line| line.strip_prefix("seed "))
+            .and_then(|seed| seed.trim().parse().ok())
+            .ok_or_else(|| format_error("missing or invalid seed"))?;
+        let area: Vec<usize> = lines.next()
+            .and_then(|line| line.strip_prefix("area "))
+            .map(|area| area.split_whitespace().filter_map(|n| n.parse().ok()).collect())
+            .unwrap_or_default();
+        if area.len() != 2 {
+            return Err(format_error("missing or invalid area"))
+        }
+
+        let mut replay = Self::new(seed, (area[0], area[1]));
+        for token in lines.flat_map(str::split_whitespace) {
+            let (input, run) = PaddleInput::parse_token(token)
+                .ok_or_else(|| ReplayError::Format(format!("invalid input '{token}'")))?;
+            if run > Self::MAX_TICKS - replay.inputs.len() {
+                return Err(format_error("longer than any match could be"))
+            }
+            replay.inputs.resize(replay.inputs.len() + run, input);
+        }
+        Ok(replay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::controller::input::PaddleInput;
+
+    use super::{Replay, ReplayError};
+
+    #[test]
+    fn text_round_trips() {
+        let mut replay = Replay::new(99, (640, 480));
+        for input in [PaddleInput::IDLE, PaddleInput::IDLE, PaddleInput::UP, PaddleInput::DOWN, PaddleInput::DOWN] {
+            replay.push(input);
+        }
+        let text = replay.to_text();
+        assert!(text.ends_with(".2 U1 D2\n"));
+        assert_eq!(Replay::from_text(&text).unwrap(), replay);
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(matches!(Replay::from_text("not a replay"), Err(ReplayError::Format(_))));
+        assert!(matches!(Replay::from_text("rust-pong replay 1\nseed 1\narea 10 10\nX3"), Err(ReplayError::Format(_))));
+        assert!(matches!(Replay::from_text("rust-pong replay 1\nseed 1\narea 10 10\nU99999999999999"), Err(ReplayError::Format(_))));
+        let runs = format!("U{} D1", Replay::MAX_TICKS);
+        assert!(matches!(Replay::from_text(&format!("rust-pong replay 1\nseed 1\narea 10 10\n{runs}")), Err(ReplayError::Format(_))));
+    }
+}
@@ -0,0 +1,108 @@
+use std::{fmt::Display, time::Duration};
+
+use crate::controller::keymap::{Action, Player};
+
+/*
+    Game states.
+        A match moves through these as players press keys and points are scored. Only PLAYING
+        advances the model; every other state holds the ball and paddles where they are.
+
+            TITLE --serve--> SERVING --serve, or the countdown running out--> PLAYING
+            PLAYING --last ball in a goal--> POINT_SCORED
+            POINT_SCORED --after POINT_SCORED_DELAY--> SERVING
+            PLAYING --pause or quit--> PAUSED --pause or serve--> PLAYING
+            PLAYING --winning point--> GAME_OVER --serve--> SERVING (a rematch)
+
+        Quit backs out of SERVING to the title, and leaves the game from TITLE, PAUSED and GAME_OVER.
+ */
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameState {
+    // a fresh game opens on the title
+    #[default]
+    TITLE,
+    // who the ball will be served towards, and the ticks left before it goes by itself, if it will
+    SERVING(Player, Option<u32>),
+    PLAYING,
+    PAUSED,
+    // who scored, and how many ticks are left before the next serve
+    POINT_SCORED(Player, u32),
+    // who won
+    GAME_OVER(Player)
+}
+
+// where an action pressed in a state leads
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    TO(GameState),
+    // a new match from the first serve, scores wiped
+    NEW_MATCH,
+    EXIT
+}
+
+impl GameState {
+    // how long the score is shown before the next serve
+    pub const POINT_SCORED_DELAY: Duration = Duration::from_secs(1);
+
+    // what pressing action does in this state, if anything
+    pub fn on_action(&self, action: Action) -> Option<Transition> {
+        match (self, action) {
+            (GameState::TITLE, Action::SERVE)                       => Some(Transition::NEW_MATCH),
+            (GameState::SERVING(..), Action::SERVE)                 => Some(Transition::TO(GameState::PLAYING)),
+            (GameState::SERVING(..), Action::QUIT)                  => Some(Transition::TO(GameState::TITLE)),
+            (GameState::PLAYING, Action::PAUSE | Action::QUIT)      => Some(Transition::TO(GameState::PAUSED)),
+            (GameState::PAUSED, Action::PAUSE | Action::SERVE)      => Some(Transition::TO(GameState::PLAYING)),
+            (GameState::GAME_OVER(_), Action::SERVE)                => Some(Transition::NEW_MATCH),
+            (GameState::TITLE | GameState::PAUSED | GameState::GAME_OVER(_), Action::QUIT) => Some(Transition::EXIT),
+            _ => None
+        }
+    }
+
+    // whether the ball and paddles move in this state
+    pub fn is_live(&self) -> bool {
+        *self == GameState::PLAYING
+    }
+}
+
+impl Display for GameState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameState::TITLE                => write!(f, "title"),
+            GameState::SERVING(p, _)        => write!(f, "serving to the {p}"),
+            GameState::PLAYING              => write!(f, "playing"),
+            GameState::PAUSED               => write!(f, "paused"),
+            GameState::POINT_SCORED(p, _)   => write!(f, "point to the {p}"),
+            GameState::GAME_OVER(p)         => write!(f, "the {p} wins"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::controller::keymap::{Action, Player};
+
+    use super::{GameState, Transition};
+
+    #[test]
+    fn escape_pauses_before_it_quits() {
+        assert_eq!(GameState::PLAYING.on_action(Action::QUIT), Some(Transition::TO(GameState::PAUSED)));
+        assert_eq!(GameState::PAUSED.on_action(Action::QUIT), Some(Transition::EXIT));
+        assert_eq!(GameState::SERVING(Player::LEFT, None).on_action(Action::QUIT), Some(Transition::TO(GameState::TITLE)));
+    }
+
+    #[test]
+    fn serve_starts_and_restarts_matches() {
+        assert_eq!(GameState::TITLE.on_action(Action::SERVE), Some(Transition::NEW_MATCH));
+        assert_eq!(GameState::SERVING(Player::LEFT, None).on_action(Action::SERVE), Some(Transition::TO(GameState::PLAYING)));
+        assert_eq!(GameState::GAME_OVER(Player::RIGHT).on_action(Action::SERVE), Some(Transition::NEW_MATCH));
+        assert_eq!(GameState::POINT_SCORED(Player::LEFT, 10).on_action(Action::SERVE), None);
+    }
+
+    #[test]
+    fn only_playing_is_live() {
+        assert!(GameState::PLAYING.is_live());
+        assert!(!GameState::PAUSED.is_live());
+        assert!(!GameState::POINT_SCORED(Player::LEFT, 1).is_live());
+    }
+}
@@ -1,10 +1,32 @@
-use crate::controller::pong_controller;
+use std::process;
 
+use clap::Parser;
+
+use crate::{cli::Cli, controller::pong_controller};
+
+pub mod cli;
 pub mod controller;
 pub mod view;
 pub mod model;
 
 
 fn main() {
-    pong_controller::run(1280, 600);
+    let session = Cli::parse().into_session().unwrap_or_else(|e| e.exit());
+    match session.headless {
+        Some(tick_budget) => {
+            let result = pong_controller::run_headless_with(session.width, session.height, session.config, session.options, tick_budget)
+                .unwrap_or_else(|e| {
+                    eprintln!("{e}");
+                    process::exit(1)
+                });
+            println!("{} | {} after {} ticks", result.plyr_score, result.comp_score, result.ticks);
+            println!("rallies: {:?}", result.rally_lengths);
+        },
+        None => {
+            if let Err(e) = pong_controller::run(session.width, session.height, session.config, session.options) {
+                eprintln!("{e}");
+                process::exit(1)
+            }
+        }
+    }
 }
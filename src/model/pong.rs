@@ -1,13 +1,14 @@
-use std::{ops::Range, collections::HashMap, sync::{Arc, Mutex}};
+use std::{ops::Range, collections::HashMap, fmt::Display, sync::{Arc, Mutex}};
 use game_objects::Position;
 
 use crate::model::pong::game_objects::objects::ObjectType;
 
-use self::game_objects::objects::GameObject;
+use self::{game_objects::objects::{GameObject, ObjectId}, collision::SweptCollision};
 
 
 pub mod vectors;
 pub mod game_objects;
+pub mod collision;
 pub const AREA_WIDTH: usize = 90;
 pub const AREA_HEIGHT: usize = 50;
 
@@ -36,23 +37,89 @@ impl Bounds {
     }
 }
 
+// names an object for as long as it is in the play area, wherever it sits among the others
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectHandle(u32);
+
+// the part an object plays in a match, for finding it without knowing when it was added
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    LEFT_PADDLE,
+    RIGHT_PADDLE,
+    LEFT_GOAL,
+    RIGHT_GOAL,
+    // the ball added last with this role, which takes it from the ball before; it leaves with its
+    // ball, so once that ball scores no object has it while older balls play on. The waiting ball
+    // is the only ball from a point until its serve, so it is always the one found then
+    BALL
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayAreaError {
+    OutOfBounds(ObjectId),
+    UnknownHandle(ObjectHandle)
+}
+
+impl Display for PlayAreaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlayAreaError::OutOfBounds(id)          => write!(f, "{id} is out of bounds"),
+            PlayAreaError::UnknownHandle(handle)    => write!(f, "no object has handle {}", handle.0),
+        }
+    }
+}
+
+impl std::error::Error for PlayAreaError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lifecycle {
+    SPAWNED,
+    DESPAWNED
+}
+
+// told about every object entering or leaving the play area, as it happens
+pub type LifecycleCallback = Box<dyn FnMut(Lifecycle, ObjectHandle, &GameObject)>;
+
+/*
+    Spawning.
+        Objects added with add_game_object are in the play area straight away. Anything that wants
+        an object to appear or disappear while the play area is being resolved (a power-up splitting
+        the ball, a projectile hitting home) uses spawn and despawn instead: their handles are
+        handed out at once, but the objects only come and go when apply_pending is called between
+        ticks, so nothing is added or removed from under a loop over the objects.
+ */
 pub struct PlayArea {
     dims : Bounds,
-    pub game_objects: Vec<GameObject>
+    game_objects: Vec<GameObject>,
+    // the handle of every object, in the same order
+    handles: Vec<ObjectHandle>,
+    roles: HashMap<Role, ObjectHandle>,
+    next_handle: u32,
+    pending_spawns: Vec<(ObjectHandle, GameObject, Option<Role>)>,
+    pending_despawns: Vec<ObjectHandle>,
+    callbacks: Vec<LifecycleCallback>,
+    max_speed: f64,
+    speed_increment: f64
 }
 
 impl PlayArea {
     pub fn new(width: usize, height: usize) -> Self {
-        Self {
-            dims: Bounds::new(width, height),
-            game_objects: Vec::new()
-        }
+        Self::from_bounds(Bounds::new(width, height))
     }
 
     pub fn from_bounds(dims: Bounds) -> Self {
         Self {
             dims,
-            game_objects: Vec::new()
+            game_objects: Vec::new(),
+            handles: Vec::new(),
+            roles: HashMap::new(),
+            next_handle: 0,
+            pending_spawns: Vec::new(),
+            pending_despawns: Vec::new(),
+            callbacks: Vec::new(),
+            max_speed: Self::MAX_SPEED,
+            speed_increment: Self::SPEED_INCREMENT
         }
     }
 }
@@ -71,8 +138,15 @@ impl PlayArea {
 
 
 impl PlayArea {
-    const MAX_SPEED: f64 = 5.0;
-    const SPEED_INCREMENT: f64 = 0.2;
+    pub const MAX_SPEED: f64 = 5.0;
+    pub const SPEED_INCREMENT: f64 = 0.2;
+
+    // how much faster a ball gets with every hit, and the speed it can't exceed
+    pub fn set_speed_limits(&mut self, max_speed: f64, speed_increment: f64) {
+        self.max_speed = max_speed;
+        self.speed_increment = speed_increment;
+    }
+
     pub fn get_width(&self) -> usize {
         self.dims.w
     }
@@ -85,19 +159,139 @@ impl PlayArea {
         (self.get_width(), self.get_height())
     }
 
-    pub fn add_game_object(&mut self, obj: GameObject) {
-        if self.object_within_bounds(&obj) {
-            self.game_objects.push(obj);
-        } else {
-            panic!("Failed to add object to PlayArea: Object is out of bounds!")
+    pub fn add_game_object(&mut self, obj: GameObject) -> Result<ObjectHandle, PlayAreaError> {
+        let handle = self.reserve_handle(&obj)?;
+        self.insert(handle, obj, None);
+        Ok(handle)
+    }
+
+    // adds obj and makes it the one playing role, taking the role from whatever had it before
+    pub fn add_with_role(&mut self, obj: GameObject, role: Role) -> Result<ObjectHandle, PlayAreaError> {
+        let handle = self.reserve_handle(&obj)?;
+        self.insert(handle, obj, Some(role));
+        Ok(handle)
+    }
+
+    // takes obj out of the play area straight away, and whatever role it had with it
+    pub fn remove(&mut self, handle: ObjectHandle) -> Result<GameObject, PlayAreaError> {
+        let i = self.index_of(handle).ok_or(PlayAreaError::UnknownHandle(handle))?;
+        self.handles.remove(i);
+        let obj = self.game_objects.remove(i);
+        self.roles.retain(|_, h| *h != handle);
+        for callback in self.callbacks.iter_mut() {
+            callback(Lifecycle::DESPAWNED, handle, &obj);
+        }
+        Ok(obj)
+    }
+
+    // obj joins the play area the next time pending changes are applied
+    pub fn spawn(&mut self, obj: GameObject) -> Result<ObjectHandle, PlayAreaError> {
+        let handle = self.reserve_handle(&obj)?;
+        self.pending_spawns.push((handle, obj, None));
+        Ok(handle)
+    }
+
+    pub fn spawn_with_role(&mut self, obj: GameObject, role: Role) -> Result<ObjectHandle, PlayAreaError> {
+        let handle = self.reserve_handle(&obj)?;
+        self.pending_spawns.push((handle, obj, Some(role)));
+        Ok(handle)
+    }
+
+    // the object leaves the play area the next time pending changes are applied
+    pub fn despawn(&mut self, handle: ObjectHandle) -> Result<(), PlayAreaError> {
+        let pending = self.pending_spawns.iter().any(|(h, _, _)| *h == handle);
+        if self.index_of(handle).is_none() && !pending {
+            return Err(PlayAreaError::UnknownHandle(handle))
         }
+        self.pending_despawns.push(handle);
+        Ok(())
     }
 
-    fn resolve_speed_increase(obj: &mut GameObject) {
-        if obj.vec.get_magnitude() > Self::MAX_SPEED {
-            obj.vec.set_magnitude(Self::MAX_SPEED);
+    // spawns first, so that something spawned and despawned in the same tick never shows up
+    pub fn apply_pending(&mut self) {
+        for (handle, obj, role) in std::mem::take(&mut self.pending_spawns) {
+            self.insert(handle, obj, role);
+        }
+        for handle in std::mem::take(&mut self.pending_despawns) {
+            // despawning the same object twice in a tick is harmless
+            let _ = self.remove(handle);
+        }
+    }
+
+    pub fn on_lifecycle(&mut self, callback: LifecycleCallback) {
+        self.callbacks.push(callback);
+    }
+
+    fn reserve_handle(&mut self, obj: &GameObject) -> Result<ObjectHandle, PlayAreaError> {
+        if !self.object_within_bounds(obj) {
+            return Err(PlayAreaError::OutOfBounds(obj.get_object_id()))
+        }
+        let handle = ObjectHandle(self.next_handle);
+        self.next_handle += 1;
+        Ok(handle)
+    }
+
+    fn insert(&mut self, handle: ObjectHandle, obj: GameObject, role: Option<Role>) {
+        self.game_objects.push(obj);
+        self.handles.push(handle);
+        if let Some(role) = role {
+            self.roles.insert(role, handle);
+        }
+        for callback in self.callbacks.iter_mut() {
+            callback(Lifecycle::SPAWNED, handle, &obj);
+        }
+    }
+
+    pub fn objects(&self) -> &[GameObject] {
+        &self.game_objects
+    }
+
+    pub fn with_handles(&self) -> impl Iterator<Item = (ObjectHandle, &GameObject)> {
+        self.handles.iter().copied().zip(self.game_objects.iter())
+    }
+
+    // how many objects of a type there will be once pending spawns and despawns are applied
+    pub fn pending_count(&self, object_type: ObjectType) -> usize {
+        let staying = |handle: &ObjectHandle| !self.pending_despawns.contains(handle);
+        let live = self.with_handles()
+            .filter(|(handle, obj)| obj.object_type == object_type && staying(handle))
+            .count();
+        let spawning = self.pending_spawns.iter()
+            .filter(|(handle, obj, _)| obj.object_type == object_type && staying(handle))
+            .count();
+        live + spawning
+    }
+
+    fn index_of(&self, handle: ObjectHandle) -> Option<usize> {
+        self.handles.iter().position(|h| *h == handle)
+    }
+
+    pub fn get(&self, handle: ObjectHandle) -> Option<&GameObject> {
+        self.index_of(handle).map(|i| &self.game_objects[i])
+    }
+
+    pub fn get_mut(&mut self, handle: ObjectHandle) -> Option<&mut GameObject> {
+        self.index_of(handle).map(|i| &mut self.game_objects[i])
+    }
+
+    pub fn handle_for(&self, role: Role) -> Option<ObjectHandle> {
+        self.roles.get(&role).copied()
+    }
+
+    // the object playing role, if any still is; see Role::BALL for how the ball's role moves on
+    pub fn by_role(&self, role: Role) -> Option<&GameObject> {
+        self.get(self.handle_for(role)?)
+    }
+
+    pub fn by_role_mut(&mut self, role: Role) -> Option<&mut GameObject> {
+        self.get_mut(self.handle_for(role)?)
+    }
+
+    fn resolve_speed_increase(obj: &mut GameObject, max_speed: f64, speed_increment: f64) {
+        if obj.vec.get_magnitude() > max_speed {
+            obj.vec.set_magnitude(max_speed);
         } else {
-            obj.vec.set_magnitude(obj.vec.get_magnitude() + Self::SPEED_INCREMENT)
+            obj.vec.set_magnitude(obj.vec.get_magnitude() + speed_increment)
         }
     }
 
@@ -106,41 +300,141 @@ impl PlayArea {
         obj.update_pos(next_pos);
     }
 
-    fn check_if_adjacent(obj: &GameObject, other: &GameObject) -> bool {
-        obj.intersecting(other)
-    } 
+    // earliest solid object that obj runs into over the coming tick; balls pass through each other
+    fn find_first_contact(&self, i: usize) -> Option<(usize, SweptCollision)> {
+        let obj = &self.game_objects[i];
+        self.game_objects.iter()
+            .enumerate()
+            .filter(|(j, other)| *j != i && other.is_solid() && other.object_type != ObjectType::BALL)
+            .filter_map(|(j, other)| obj.sweep(other).map(|collision| (j, collision)))
+            .min_by(|(_, a), (_, b)| a.get_time().total_cmp(&b.get_time()))
+    }
 
-    fn resolve_interact(obj: &mut GameObject, other: &GameObject) {
-        if obj.object_type == ObjectType::BALL {
-            if Self::check_if_adjacent(obj, other) { 
-                println!("{} interacting with {}: ", obj.get_id(), other.get_id());
-                obj.interact_with(other);
-                Self::resolve_speed_increase(obj);
-            }
-        }  
+    // moves obj up to the point of contact instead of letting it sink into (or pass through) other
+    fn resolve_contact(&mut self, i: usize, j: usize, collision: SweptCollision) {
+        let other = self.game_objects[j];
+        let (max_speed, speed_increment) = (self.max_speed, self.speed_increment);
+        let obj = &mut self.game_objects[i];
+        let mut contact_pos = obj.pos;
+        let next_pos = obj.next_pos();
+        let manifold = collision.get_manifold();
+        contact_pos.inc_x_pos((next_pos.get_x_pos() - obj.pos.get_x_pos()) * collision.get_time());
+        contact_pos.inc_y_pos((next_pos.get_y_pos() - obj.pos.get_y_pos()) * collision.get_time());
+        // push back out of anything it was already stuck inside of
+        contact_pos.inc_x_pos(manifold.get_normal().x_component() * manifold.get_penetration());
+        contact_pos.inc_y_pos(manifold.get_normal().y_component() * manifold.get_penetration());
+        obj.update_pos(contact_pos);
+        obj.interact_with(&other, &manifold);
+        Self::resolve_speed_increase(obj, max_speed, speed_increment);
     }
 
-    pub fn resolve_object_behaviors(&mut self) {
-        
+    // moves everything on by a tick, returning which objects ran into which
+    pub fn resolve_object_behaviors(&mut self) -> Vec<(ObjectHandle, ObjectHandle)> {
+        let mut contacts = Vec::new();
         let number_objects = self.game_objects.len();
         for i in 0..number_objects {
-            for j in 0..number_objects {
-                if i != j {
-                    let other = self.game_objects[j];
-                    Self::resolve_interact(&mut self.game_objects[i], &other);
-                }
+            let contact = if self.game_objects[i].object_type == ObjectType::BALL {
+                self.find_first_contact(i)
+            } else {
+                None
+            };
+            match contact {
+                Some((j, collision)) => {
+                    self.resolve_contact(i, j, collision);
+                    contacts.push((self.handles[i], self.handles[j]));
+                },
+                None => Self::resolve_movement(&mut self.game_objects[i])
             }
-            Self::resolve_movement(&mut self.game_objects[i])
-            
-            // resolve interaction
-            
-            
-            // resolve movement
-            
         }
+        contacts
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::model::pong::{PlayArea, PlayAreaError, Lifecycle, Role, game_objects::{GameObjectFactory, objects::ObjectType}, vectors::EuclideanVector};
 
+    #[test]
+    fn ball_at_max_speed_stops_at_wall() {
+        let mut factory = GameObjectFactory::new();
+        let mut play_area = PlayArea::new(200, 200);
+        let mut wall = factory.create(ObjectType::WALL, 150, 0, 10, 200);
+        wall.set_surface_normal(EuclideanVector::new(1.0, 180.0));
+        play_area.add_game_object(wall).unwrap();
+        let mut ball = factory.create(ObjectType::BALL, 125, 90, 20, 20);
+        ball.vec = EuclideanVector::new(PlayArea::MAX_SPEED * 2.0, 0.0);
+        play_area.add_game_object(ball).unwrap();
+
+        play_area.resolve_object_behaviors();
+        let ball = play_area.game_objects[1];
+        assert!((ball.x_extent() - 150.0).abs() < 1e-9);
+        assert!(ball.vec.x_component() < 0.0);
+    }
+
+    #[test]
+    fn ball_landing_on_paddle_top_bounces_upwards() {
+        let mut factory = GameObjectFactory::new();
+        let mut play_area = PlayArea::new(200, 200);
+        play_area.add_game_object(factory.create(ObjectType::PADDLE, 100, 100, 10, 50)).unwrap();
+        let mut ball = factory.create(ObjectType::BALL, 95, 78, 20, 20);
+        ball.vec = EuclideanVector::new(3.0, 80.0);
+        play_area.add_game_object(ball).unwrap();
+
+        play_area.resolve_object_behaviors();
+        let ball = play_area.game_objects[1];
+        assert!(ball.vec.y_component() < 0.0);
+        assert!(ball.vec.x_component() > 0.0);
+        assert!(!ball.intersecting(&play_area.game_objects[0]));
+    }
 
+    #[test]
+    fn handles_and_roles_survive_other_insertions() {
+        let mut factory = GameObjectFactory::new();
+        let mut play_area = PlayArea::new(200, 200);
+        play_area.add_game_object(factory.create(ObjectType::CUSTOM, 0, 0, 5, 5)).unwrap();
+        let ball = factory.create(ObjectType::BALL, 50, 60, 10, 10);
+        let handle = play_area.add_with_role(ball, Role::BALL).unwrap();
+        play_area.add_game_object(factory.create(ObjectType::CUSTOM, 100, 100, 5, 5)).unwrap();
 
+        assert_eq!(play_area.get(handle).unwrap().pos.as_tuple(), (50.0, 60.0));
+        assert_eq!(play_area.handle_for(Role::BALL), Some(handle));
+        assert!(play_area.by_role(Role::LEFT_PADDLE).is_none());
+    }
+
+    #[test]
+    fn spawns_and_despawns_wait_for_apply_pending() {
+        let mut factory = GameObjectFactory::new();
+        let mut play_area = PlayArea::new(200, 200);
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let seen = Rc::clone(&events);
+        play_area.on_lifecycle(Box::new(move |event, handle, _| seen.borrow_mut().push((event, handle))));
+
+        let first = play_area.spawn_with_role(factory.create(ObjectType::BALL, 10, 10, 5, 5), Role::BALL).unwrap();
+        assert!(play_area.get(first).is_none());
+        play_area.apply_pending();
+        assert_eq!(play_area.handle_for(Role::BALL), Some(first));
+
+        let second = play_area.spawn(factory.create(ObjectType::BALL, 20, 20, 5, 5)).unwrap();
+        play_area.despawn(first).unwrap();
+        assert!(play_area.get(first).is_some());
+        play_area.apply_pending();
+        assert!(play_area.get(first).is_none());
+        assert!(play_area.by_role(Role::BALL).is_none());
+        assert_eq!(play_area.objects().len(), 1);
+
+        assert_eq!(*events.borrow(), vec![(Lifecycle::SPAWNED, first), (Lifecycle::SPAWNED, second), (Lifecycle::DESPAWNED, first)]);
+        assert_eq!(play_area.despawn(first), Err(PlayAreaError::UnknownHandle(first)));
+    }
+
+    #[test]
+    fn adding_out_of_bounds_is_an_error() {
+        let mut factory = GameObjectFactory::new();
+        let mut play_area = PlayArea::new(200, 200);
+        let stray = factory.create(ObjectType::CUSTOM, 300, 10, 5, 5);
+        assert_eq!(play_area.add_game_object(stray), Err(PlayAreaError::OutOfBounds(stray.get_object_id())));
+        assert_eq!(play_area.spawn(stray), Err(PlayAreaError::OutOfBounds(stray.get_object_id())));
+        assert!(play_area.objects().is_empty());
+    }
+}
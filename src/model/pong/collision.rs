@@ -0,0 +1,227 @@
+use super::{game_objects::objects::GameObject, vectors::EuclideanVector};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y
+}
+
+/*
+    Collision manifold.
+        Describes how two boxes touch: the axis along which they overlap the least, how deep that
+        overlap is, and the unit normal pointing out of the struck object towards the striking one.
+        Behaviors use the normal to decide which way to bounce instead of guessing from velocities.
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct Manifold {
+    penetration: f64,
+    axis: Axis,
+    normal: EuclideanVector
+}
+
+impl Manifold {
+    pub fn new(penetration: f64, axis: Axis, normal: EuclideanVector) -> Self {
+        Self {penetration, axis, normal}
+    }
+
+    pub fn get_penetration(&self) -> f64 {
+        self.penetration
+    }
+
+    pub fn get_axis(&self) -> Axis {
+        self.axis
+    }
+
+    pub fn get_normal(&self) -> EuclideanVector {
+        self.normal
+    }
+}
+
+fn sign(u: f64) -> f64 {
+    if u < 0.0 {-1.0} else {1.0}
+}
+
+// overlap of two closed-open intervals that treats both sides alike; touching edges do not count
+fn interval_overlap(min: f64, max: f64, other_min: f64, other_max: f64) -> Option<f64> {
+    if min < other_max && other_min < max {
+        Some(max.min(other_max) - min.max(other_min))
+    } else {
+        None
+    }
+}
+
+// symmetric AABB overlap test; the normal points out of `other` towards `obj`
+pub fn overlap(obj: &GameObject, other: &GameObject) -> Option<Manifold> {
+    let x_overlap = interval_overlap(obj.pos.get_x_pos(), obj.x_extent(), other.pos.get_x_pos(), other.x_extent())?;
+    let y_overlap = interval_overlap(obj.pos.get_y_pos(), obj.y_extent(), other.pos.get_y_pos(), other.y_extent())?;
+    let (mid, other_mid) = (obj.midpoint(), other.midpoint());
+    Some(if x_overlap < y_overlap {
+        Manifold::new(x_overlap, Axis::X, EuclideanVector::from_components(sign(mid.get_x_pos() - other_mid.get_x_pos()), 0.0))
+    } else {
+        Manifold::new(y_overlap, Axis::Y, EuclideanVector::from_components(0.0, sign(mid.get_y_pos() - other_mid.get_y_pos())))
+    })
+}
+
+/*
+    Swept AABB collision.
+        Rather than checking for overlap at the discrete positions an object occupies at each tick,
+        the motion of the object over a tick is treated as a ray and cast against the obstacle's box.
+        This yields the fraction of the tick at which the two first touch (the time of impact) and
+        the face that was struck (the contact normal), so fast objects can no longer skip over thin ones.
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct SweptCollision {
+    time: f64,
+    manifold: Manifold
+}
+
+impl SweptCollision {
+    pub fn new(time: f64, manifold: Manifold) -> Self {
+        Self {time, manifold}
+    }
+
+    // fraction of the tick, in [0, 1], at which contact happens
+    pub fn get_time(&self) -> f64 {
+        self.time
+    }
+
+    // contact at the time of impact; only has a penetration if the objects started out overlapping
+    pub fn get_manifold(&self) -> Manifold {
+        self.manifold
+    }
+
+    // unit vector pointing out of the struck face, towards the moving object
+    pub fn get_normal(&self) -> EuclideanVector {
+        self.manifold.get_normal()
+    }
+}
+
+// entry and exit times along a single axis, or None if the object never overlaps on that axis
+fn axis_times(min: f64, max: f64, other_min: f64, other_max: f64, delta: f64) -> Option<(f64, f64)> {
+    if delta > 0.0 {
+        Some(((other_min - max) / delta, (other_max - min) / delta))
+    } else if delta < 0.0 {
+        Some(((other_max - min) / delta, (other_min - max) / delta))
+    } else if min < other_max && max > other_min {
+        Some((f64::NEG_INFINITY, f64::INFINITY))
+    } else {
+        None
+    }
+}
+
+// casts the motion of `moving` over the coming tick, relative to `obstacle`, against the obstacle's box
+pub fn sweep(moving: &GameObject, obstacle: &GameObject) -> Option<SweptCollision> {
+    let (next, other_next) = (moving.next_pos(), obstacle.next_pos());
+    let dx = (next.get_x_pos() - moving.pos.get_x_pos()) - (other_next.get_x_pos() - obstacle.pos.get_x_pos());
+    let dy = (next.get_y_pos() - moving.pos.get_y_pos()) - (other_next.get_y_pos() - obstacle.pos.get_y_pos());
+
+    let (x_entry, x_exit) = axis_times(moving.pos.get_x_pos(), moving.x_extent(), obstacle.pos.get_x_pos(), obstacle.x_extent(), dx)?;
+    let (y_entry, y_exit) = axis_times(moving.pos.get_y_pos(), moving.y_extent(), obstacle.pos.get_y_pos(), obstacle.y_extent(), dy)?;
+
+    let entry = x_entry.max(y_entry);
+    let exit = x_exit.min(y_exit);
+    if entry > exit || exit <= 0.0 || entry > 1.0 {
+        return None
+    }
+
+    let (time, manifold) = if entry < 0.0 {
+        // already overlapping at the start of the tick
+        (0.0, overlap(moving, obstacle)?)
+    } else if x_entry > y_entry {
+        (entry, Manifold::new(0.0, Axis::X, EuclideanVector::from_components(-sign(dx), 0.0)))
+    } else {
+        (entry, Manifold::new(0.0, Axis::Y, EuclideanVector::from_components(0.0, -sign(dy))))
+    };
+
+    // only report contacts the object is moving into, never ones it is already leaving
+    let normal = manifold.get_normal();
+    if normal.x_component() * dx + normal.y_component() * dy >= 0.0 {
+        return None
+    }
+    Some(SweptCollision::new(time, manifold))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::pong::{game_objects::{GameObjectFactory, objects::{GameObject, ObjectType}}, vectors::EuclideanVector};
+
+    use super::{sweep, overlap, Axis};
+
+    fn ball_at(factory: &mut GameObjectFactory, x: usize, y: usize, vec: EuclideanVector) -> GameObject {
+        let mut ball = factory.create(ObjectType::BALL, x, y, 20, 20);
+        ball.vec = vec;
+        ball
+    }
+
+    #[test]
+    fn fast_ball_does_not_tunnel_through_paddle() {
+        let mut factory = GameObjectFactory::new();
+        let ball = ball_at(&mut factory, 100, 100, EuclideanVector::new(200.0, 0.0));
+        let paddle = factory.create(ObjectType::PADDLE, 200, 90, 10, 50);
+        assert!(!ball.intersecting(&paddle));
+
+        let collision = sweep(&ball, &paddle).expect("ball should hit the paddle");
+        assert!((collision.get_time() - 0.4).abs() < 1e-9);
+        assert!((collision.get_normal().x_component() + 1.0).abs() < 1e-9);
+        assert!(collision.get_normal().y_component().abs() < 1e-9);
+    }
+
+    #[test]
+    fn moving_away_is_not_a_collision() {
+        let mut factory = GameObjectFactory::new();
+        let ball = ball_at(&mut factory, 180, 100, EuclideanVector::new(5.0, 180.0));
+        let paddle = factory.create(ObjectType::PADDLE, 200, 90, 10, 50);
+        assert!(sweep(&ball, &paddle).is_none());
+    }
+
+    #[test]
+    fn ball_out_of_reach_is_not_a_collision() {
+        let mut factory = GameObjectFactory::new();
+        let ball = ball_at(&mut factory, 100, 100, EuclideanVector::new(5.0, 0.0));
+        let paddle = factory.create(ObjectType::PADDLE, 200, 90, 10, 50);
+        assert!(sweep(&ball, &paddle).is_none());
+    }
+
+    #[test]
+    fn overlap_catches_objects_straddling_each_other() {
+        let mut factory = GameObjectFactory::new();
+        // a thin tall bar crossing a wide short one; no corner of either lies inside the other
+        let tall = factory.create(ObjectType::CUSTOM, 50, 0, 10, 100);
+        let wide = factory.create(ObjectType::CUSTOM, 0, 40, 100, 10);
+        assert!(overlap(&tall, &wide).is_some());
+        assert!(tall.intersecting(&wide) && wide.intersecting(&tall));
+    }
+
+    #[test]
+    fn overlap_is_symmetric() {
+        let mut factory = GameObjectFactory::new();
+        let ball = factory.create(ObjectType::BALL, 185, 100, 20, 20);
+        let paddle = factory.create(ObjectType::PADDLE, 200, 90, 10, 50);
+        let (a, b) = (overlap(&ball, &paddle).unwrap(), overlap(&paddle, &ball).unwrap());
+        assert_eq!(a.get_penetration(), b.get_penetration());
+        assert_eq!(a.get_axis(), Axis::X);
+        assert_eq!(a.get_axis(), b.get_axis());
+        assert!((a.get_normal().x_component() + 1.0).abs() < 1e-9);
+        assert!((b.get_normal().x_component() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn touching_edges_do_not_overlap() {
+        let mut factory = GameObjectFactory::new();
+        let ball = factory.create(ObjectType::BALL, 180, 100, 20, 20);
+        let paddle = factory.create(ObjectType::PADDLE, 200, 90, 10, 50);
+        assert!(overlap(&ball, &paddle).is_none());
+        assert!(!ball.intersecting(&paddle) && !paddle.intersecting(&ball));
+    }
+
+    #[test]
+    fn hits_top_face_from_above() {
+        let mut factory = GameObjectFactory::new();
+        let ball = ball_at(&mut factory, 300, 100, EuclideanVector::new(50.0, 90.0));
+        let wall = factory.create(ObjectType::WALL, 0, 130, 600, 10);
+
+        let collision = sweep(&ball, &wall).expect("ball should hit the wall");
+        assert!((collision.get_time() - 0.2).abs() < 1e-9);
+        assert!((collision.get_normal().y_component() + 1.0).abs() < 1e-9);
+    }
+}
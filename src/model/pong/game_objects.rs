@@ -56,6 +56,14 @@ impl Position {
     pub fn set_y(&mut self, new_y: f64) {
         self.y_pos = new_y;
     }
+
+    // linear interpolation towards `other`, where t = 0.0 is self and t = 1.0 is other
+    pub fn lerp(&self, other: &Position, t: f64) -> Position {
+        Position {
+            x_pos: self.x_pos + (other.x_pos - self.x_pos) * t,
+            y_pos: self.y_pos + (other.y_pos - self.y_pos) * t
+        }
+    }
 }
 
 
@@ -105,8 +113,8 @@ impl GameObjectFactory {
     fn fetch_vector(obj_type: ObjectType) -> EuclideanVector {
         match obj_type {
             ObjectType::BALL    => EuclideanVector::new(1.0, 45.0),
-            ObjectType::PADDLE  => EuclideanVector::new(1.0, 0.0),
-            ObjectType::WALL    => EuclideanVector::new(1.0, 45.0),
+            ObjectType::PADDLE  => EuclideanVector::new(0.0, 0.0),
+            ObjectType::WALL    => EuclideanVector::new(0.0, 0.0),
             ObjectType::GOAL    => EuclideanVector::new(0.0, 0.0),
             ObjectType::CUSTOM  => EuclideanVector::new(0.0, 0.0),
         }
@@ -114,7 +122,7 @@ impl GameObjectFactory {
     fn fetch_behavior(obj_type: ObjectType) -> ObjectBehavior {
         match obj_type {
             ObjectType::BALL    => ObjectBehavior::new( 
-                ObjectInteractBehavior::create(ObjectInteractBehaviors::LOSSLESS_COLLISION),
+                ObjectInteractBehavior::create(ObjectInteractBehaviors::ENGLISH),
                 ObjectMovementBehavior::create(ObjectMovementBehaviors::MOVING)
             ),
             ObjectType::PADDLE  => ObjectBehavior::new( 
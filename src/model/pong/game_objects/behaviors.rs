@@ -1,3 +1,5 @@
+use crate::model::pong::{collision::{Manifold, Axis}, vectors::EuclideanVector};
+
 use super::{objects::GameObject, Position};
 
 #[derive(Clone, Copy)]
@@ -11,7 +13,7 @@ impl ObjectBehavior {
         Self {interact_behavior, movement_behavior}
     }
 
-    pub fn set_interact_behavior(&mut self, behavior: fn(&mut GameObject, &GameObject)) {
+    pub fn set_interact_behavior(&mut self, behavior: fn(&mut GameObject, &GameObject, &Manifold)) {
         self.interact_behavior.behavior = behavior
     }
 
@@ -22,8 +24,8 @@ impl ObjectBehavior {
 }
 
 impl ObjectBehavior {
-    pub fn interact(&self, the_changed: &mut GameObject, the_changer: &GameObject){
-        self.interact_behavior.call(the_changed, the_changer)
+    pub fn interact(&self, the_changed: &mut GameObject, the_changer: &GameObject, manifold: &Manifold){
+        self.interact_behavior.call(the_changed, the_changer, manifold)
     } 
 
     pub fn movement(&self, game_object: &GameObject) -> Position {
@@ -31,26 +33,79 @@ impl ObjectBehavior {
     }
 }
 
+/*
+    English.
+        How a paddle sends the ball back. Rather than mirroring the ball, the return angle is set by
+        where the ball struck: dead center goes straight back, the very tip leaves at max_deflection.
+        Spin is the extra angle (in degrees) added per unit of the paddle's own vertical velocity;
+        a spin of 0.0 turns it off.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct English {
+    max_deflection: f64,
+    spin: f64
+}
+
+impl English {
+    pub const DEFAULT_MAX_DEFLECTION: f64 = 60.0;
+
+    pub fn new(max_deflection: f64, spin: f64) -> Self {
+        Self {max_deflection, spin}
+    }
+
+    pub fn get_max_deflection(&self) -> f64 {
+        self.max_deflection
+    }
+
+    pub fn get_spin(&self) -> f64 {
+        self.spin
+    }
+}
+
+impl Default for English {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_DEFLECTION, 0.0)
+    }
+}
+
 pub enum ObjectInteractBehaviors {
     NOTHING,
-    LOSSLESS_COLLISION
+    LOSSLESS_COLLISION,
+    ENGLISH
 }
 
 impl ObjectInteractBehaviors {
-    pub fn nothing(the_changed: &mut GameObject, the_changer: &GameObject) {}
+    pub fn nothing(the_changed: &mut GameObject, the_changer: &GameObject, _manifold: &Manifold) {}
     // collision behavior
-    pub fn lossless_collision(the_changed: &mut GameObject, the_changer: &GameObject) {
-        the_changed.vec.collide_with(the_changer.vec)
+    pub fn lossless_collision(the_changed: &mut GameObject, the_changer: &GameObject, manifold: &Manifold) {
+        the_changed.vec.reflect(the_changer.contact_normal(manifold))
+    }
+
+    // bounces off of anything with english according to where it was struck, otherwise collides losslessly
+    pub fn english(the_changed: &mut GameObject, the_changer: &GameObject, manifold: &Manifold) {
+        let english = match the_changer.get_english() {
+            // the top and bottom edges of a paddle are just walls
+            Some(english) if manifold.get_axis() == Axis::X => english,
+            _ => return Self::lossless_collision(the_changed, the_changer, manifold)
+        };
+        let half_height = the_changer.dim.get_height() / 2.0;
+        let offset = ((the_changed.midpoint().get_y_pos() - the_changer.midpoint().get_y_pos()) / half_height).clamp(-1.0, 1.0);
+        let max_deflection = english.get_max_deflection();
+        let deflection = (offset * max_deflection + the_changer.vec.y_component() * english.get_spin())
+            .clamp(-max_deflection, max_deflection);
+        // the manifold normal points from the paddle towards the ball, i.e. the way the ball should leave
+        let angle = if manifold.get_normal().x_component() > 0.0 {deflection} else {180.0 - deflection};
+        the_changed.vec.update(EuclideanVector::new(the_changed.vec.get_magnitude(), angle))
     }
     
 }
 #[derive(Clone, Copy)]
 pub struct ObjectInteractBehavior {
-    behavior: fn(&mut GameObject, &GameObject)
+    behavior: fn(&mut GameObject, &GameObject, &Manifold)
 }
 
 impl ObjectInteractBehavior {
-    pub fn new(behavior: fn(&mut GameObject, &GameObject)) -> ObjectInteractBehavior {
+    pub fn new(behavior: fn(&mut GameObject, &GameObject, &Manifold)) -> ObjectInteractBehavior {
         Self {behavior}
     }
 
@@ -59,12 +114,13 @@ impl ObjectInteractBehavior {
             behavior: match interact_behavior {
                     ObjectInteractBehaviors::NOTHING => ObjectInteractBehaviors::nothing,
                     ObjectInteractBehaviors::LOSSLESS_COLLISION => ObjectInteractBehaviors::lossless_collision,
+                    ObjectInteractBehaviors::ENGLISH => ObjectInteractBehaviors::english,
             }
         }
     }
 
-    fn call(&self, the_changed: &mut GameObject, the_changer: &GameObject) {
-        (self.behavior)(the_changed, the_changer)
+    fn call(&self, the_changed: &mut GameObject, the_changer: &GameObject, manifold: &Manifold) {
+        (self.behavior)(the_changed, the_changer, manifold)
     }
 }
 
@@ -114,3 +170,45 @@ impl ObjectMovementBehavior {
         (self.behavior)(game_object)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::model::pong::{game_objects::{GameObjectFactory, objects::{GameObject, ObjectType}}, vectors::EuclideanVector};
+
+    use super::{English, ObjectInteractBehaviors};
+
+    fn hit(ball_y: usize, paddle_velocity: f64, english: English) -> GameObject {
+        let mut factory = GameObjectFactory::new();
+        let mut paddle = factory.create(ObjectType::PADDLE, 100, 100, 10, 50);
+        paddle.set_english(english);
+        paddle.vec = EuclideanVector::from_components(0.0, paddle_velocity);
+        // ball sunk a pixel into the right face of the paddle
+        let mut ball = factory.create(ObjectType::BALL, 109, ball_y, 20, 20);
+        ball.vec = EuclideanVector::new(4.0, 160.0);
+        let manifold = ball.manifold(&paddle).unwrap();
+        ObjectInteractBehaviors::english(&mut ball, &paddle, &manifold);
+        ball
+    }
+
+    #[test]
+    fn center_hit_returns_straight() {
+        let ball = hit(115, 0.0, English::default());
+        assert!((ball.vec.get_angle() - 0.0).abs() < 1e-9);
+        assert!((ball.vec.get_magnitude() - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn edge_hit_deflects_by_max() {
+        let ball = hit(140, 0.0, English::new(45.0, 0.0));
+        assert!((ball.vec.get_angle() - 45.0).abs() < 1e-9);
+        let ball = hit(90, 0.0, English::new(45.0, 0.0));
+        assert!((ball.vec.get_angle() + 45.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn paddle_momentum_adds_spin() {
+        let still = hit(115, 0.0, English::new(60.0, 4.0));
+        let moving = hit(115, 2.5, English::new(60.0, 4.0));
+        assert!((moving.vec.get_angle() - still.vec.get_angle() - 10.0).abs() < 1e-9);
+    }
+}
@@ -2,9 +2,9 @@ use std::fmt::Display;
 use std::hash::{Hash};
 use std::ops::Range;
 
-use crate::{ model::pong::{vectors::EuclideanVector}};
+use crate::{ model::pong::{vectors::EuclideanVector, collision::{self, SweptCollision, Manifold}}};
 
-use super::{Position, behaviors::ObjectBehavior, ObjectDimensions};
+use super::{Position, behaviors::{ObjectBehavior, English}, ObjectDimensions};
 
 #[derive(Clone, Copy, Debug, Hash)]
 pub enum ObjectType {
@@ -35,7 +35,7 @@ impl Display for ObjectType {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ObjectId {
     ID(ObjectType, u32)
 }
@@ -72,12 +72,14 @@ impl Display for ObjectId {
     last_pos: Position,
     pub dim: ObjectDimensions,
     pub vec: EuclideanVector,
-    pub behavior: ObjectBehavior
+    pub behavior: ObjectBehavior,
+    surface_normal: Option<EuclideanVector>,
+    english: Option<English>
 }
 
 impl GameObject {
     pub fn new(id: ObjectId, object_type: ObjectType, pos: Position, dim: ObjectDimensions, vec: EuclideanVector, behavior: ObjectBehavior) -> Self {
-        Self {id, object_type, pos, last_pos: pos, dim, vec, behavior}
+        Self {id, object_type, pos, last_pos: pos, dim, vec, behavior, surface_normal: None, english: None}
     }
 }
 
@@ -99,7 +101,7 @@ impl GameObject {
     }
 
     pub fn midpoint(&self) -> Position {
-        Position::new(self.pos.x_pos + self.x_extent()/2.0, self.pos.y_pos + self.y_extent()/2.0)
+        Position::new(self.pos.x_pos + self.dim.get_width()/2.0, self.pos.y_pos + self.dim.get_height()/2.0)
     }
 
     pub fn dim_boundaries(&self) -> (Range<f64>, Range<f64>) {
@@ -112,6 +114,10 @@ impl GameObject {
         self.id.to_string()
     }
 
+    pub fn get_object_id(&self) -> ObjectId {
+        self.id
+    }
+
     pub fn kill_velocity(&mut self) {
         self.vec.set_angle(0.0);
         self.vec.set_magnitude(0.0);
@@ -132,9 +138,48 @@ impl GameObject {
         self.last_pos
     }
 
-    pub fn interact_with(&mut self, other: &Self) {
+    // moves the object without leaving a trail, so interpolation doesn't smear it across the area
+    pub fn teleport(&mut self, new_pos: Position) {
+        self.last_pos = new_pos;
+        self.pos = new_pos
+    }
+
+    // position between the last tick and the current one, alpha being the fraction of a tick elapsed
+    pub fn interpolated_pos(&self, alpha: f64) -> Position {
+        self.last_pos.lerp(&self.pos, alpha)
+    }
+
+    // declares the direction the object's surface faces, overriding the face found by collision detection
+    pub fn set_surface_normal(&mut self, normal: EuclideanVector) {
+        self.surface_normal = Some(normal.normalized())
+    }
+
+    pub fn get_surface_normal(&self) -> Option<EuclideanVector> {
+        self.surface_normal
+    }
+
+    // lets the object steer what strikes it, the way a paddle does
+    pub fn set_english(&mut self, english: English) {
+        self.english = Some(english)
+    }
+
+    pub fn get_english(&self) -> Option<English> {
+        self.english
+    }
+
+    // normal to bounce off of when struck, given how the contact was detected
+    pub fn contact_normal(&self, manifold: &Manifold) -> EuclideanVector {
+        self.surface_normal.unwrap_or_else(|| manifold.get_normal())
+    }
+
+    // goals and the like register overlaps, but nothing should bounce off of them
+    pub fn is_solid(&self) -> bool {
+        !matches!(self.object_type, ObjectType::GOAL)
+    }
+
+    pub fn interact_with(&mut self, other: &Self, manifold: &Manifold) {
         let behavior = self.behavior;
-        behavior.interact(self, other)
+        behavior.interact(self, other, manifold)
     }
 
     /* 
@@ -152,10 +197,17 @@ impl GameObject {
     */
 
     pub fn intersecting(&self, other: &Self) -> bool {
-        let (other_x_range, other_y_range) = other.dim_boundaries();
-        (other_x_range.contains(&self.pos.get_x_pos()) || other_x_range.contains(&self.x_extent()))
-        && 
-        (other_y_range.contains(&self.pos.get_y_pos()) || other_y_range.contains(&self.y_extent()))
+        self.manifold(other).is_some()
+    }
+
+    // how self and other overlap, if they do; the normal points out of other towards self
+    pub fn manifold(&self, other: &Self) -> Option<Manifold> {
+        collision::overlap(self, other)
+    }
+
+    // checks the path between pos and next_pos for contact with other, rather than just the endpoints
+    pub fn sweep(&self, other: &Self) -> Option<SweptCollision> {
+        collision::sweep(self, other)
     }
     
     
@@ -22,6 +22,20 @@ impl PartialEq for EuclideanVector {
 
 impl Eq for EuclideanVector {}
 
+impl EuclideanVector {
+    pub const DEFAULT_EPSILON: f64 = 1e-9;
+
+    /*
+        Approximate equality.
+            Compares the x and y components rather than magnitude and angle, so vectors that only
+            differ by a full turn (45 vs -315) or that come out of a cos/sin round trip still match.
+     */
+    pub fn approx_eq(&self, other: &Self, eps: f64) -> bool {
+        (self.x_component() - other.x_component()).abs() <= eps
+        && (self.y_component() - other.y_component()).abs() <= eps
+    }
+}
+
 impl EuclideanVector {
     pub fn new(magnitude: f64, angle: f64) -> Self {
         Self {
@@ -108,10 +122,7 @@ impl ops::Mul<f64> for EuclideanVector {
 impl ops::Add<EuclideanVector> for EuclideanVector {
     type Output = EuclideanVector;
     fn add(self, rhs: EuclideanVector) -> Self::Output {
-        Self::from_components(
-            self.x_component() + rhs.x_component(),
-            self.y_component() + rhs.y_component()
-        )
+        (Vec2::from(self) + Vec2::from(rhs)).into()
     }
 }
 
@@ -119,10 +130,7 @@ impl ops::Add<EuclideanVector> for EuclideanVector {
 impl ops::Sub<EuclideanVector> for EuclideanVector {
     type Output = EuclideanVector;
     fn sub(self, rhs: EuclideanVector) -> Self::Output {
-        Self::from_components(
-            self.x_component() - rhs.x_component(),
-            self.y_component() - rhs.y_component() 
-        )
+        (Vec2::from(self) - Vec2::from(rhs)).into()
     }
 }
 
@@ -133,17 +141,184 @@ impl EuclideanVector {
         self.angle = new_vec.angle;
     }
 
+    // mirrors the component along opposing_vector, keeping the magnitude
     pub fn collide_with(&mut self, opposing_vector: EuclideanVector) {
-        let v = self.normalized();
-        let opposing_vector = opposing_vector.normalized();
-        let u = opposing_vector*((v * opposing_vector) / (opposing_vector*opposing_vector));
-        let w = v - u;
-        self.set_angle((w - u).get_angle());
+        self.reflect(opposing_vector)
+    }
+
+    /*
+        Reflection against a surface.
+            r = v - 2(v . n)n, where n is the unit normal of the surface struck.
+            The sign of the normal doesn't matter, and a zero normal leaves the vector untouched.
+     */
+    pub fn reflect(&mut self, surface_normal: EuclideanVector) {
+        self.update(self.reflected(surface_normal))
+    }
+
+    pub fn reflected(&self, surface_normal: EuclideanVector) -> Self {
+        if surface_normal.get_magnitude() == 0.0 {
+            return *self
+        }
+        let reflection = Vec2::from(*self).reflect(Vec2::from(surface_normal));
+        Self::new(self.magnitude, reflection.angle())
+    }
+}
+
+/*
+    Cartesian counterpart to EuclideanVector.
+        Stores the x and y components directly, so arithmetic never has to go through trigonometry.
+        Angles are in degrees and follow the same convention as EuclideanVector (atan2 of y over x),
+        which keeps conversions between the two exact up to floating point rounding.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Vec2 {
+    pub x: f64,
+    pub y: f64
+}
+
+impl Display for Vec2 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 {x: 0.0, y: 0.0};
+
+    pub fn new(x: f64, y: f64) -> Self {
+        Self {x, y}
+    }
+
+    // unit vector pointing at the given angle, in degrees
+    pub fn from_angle(angle: f64) -> Self {
+        let radians = angle.to_radians();
+        Self::new(radians.cos(), radians.sin())
+    }
+
+    pub fn dot(&self, other: Vec2) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    // z component of the 3D cross product; positive when other is clockwise of self on screen
+    pub fn cross(&self, other: Vec2) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    // self rotated a quarter turn
+    pub fn perpendicular(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    pub fn length_squared(&self) -> f64 {
+        self.dot(*self)
+    }
+
+    pub fn length(&self) -> f64 {
+        self.length_squared().sqrt()
+    }
+
+    pub fn angle(&self) -> f64 {
+        self.y.atan2(self.x).to_degrees()
+    }
+
+    // unit vector in the same direction; the zero vector has no direction and stays zero
+    pub fn normalize(&self) -> Self {
+        let length = self.length();
+        if length == 0.0 {
+            *self
+        } else {
+            *self / length
+        }
+    }
+
+    pub fn lerp(&self, other: Vec2, t: f64) -> Self {
+        *self + (other - *self) * t
+    }
+
+    // mirror image against a surface with the given normal, which need not be unit length
+    pub fn reflect(&self, normal: Vec2) -> Self {
+        let n = normal.normalize();
+        *self - n * (2.0 * self.dot(n))
+    }
+
+    pub fn rotate(&self, angle: f64) -> Self {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        Self::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+}
+
+impl From<EuclideanVector> for Vec2 {
+    fn from(vec: EuclideanVector) -> Self {
+        Self::new(vec.x_component(), vec.y_component())
+    }
+}
+
+impl From<Vec2> for EuclideanVector {
+    fn from(vec: Vec2) -> Self {
+        EuclideanVector::from_components(vec.x, vec.y)
+    }
+}
+
+impl ops::Add<Vec2> for Vec2 {
+    type Output = Vec2;
+    fn add(self, rhs: Vec2) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl ops::Sub<Vec2> for Vec2 {
+    type Output = Vec2;
+    fn sub(self, rhs: Vec2) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl ops::Neg for Vec2 {
+    type Output = Vec2;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl ops::Mul<f64> for Vec2 {
+    type Output = Vec2;
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl ops::Mul<Vec2> for f64 {
+    type Output = Vec2;
+    fn mul(self, rhs: Vec2) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl ops::Div<f64> for Vec2 {
+    type Output = Vec2;
+    fn div(self, rhs: f64) -> Self::Output {
+        Self::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl ops::AddAssign<Vec2> for Vec2 {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs
+    }
+}
+
+impl ops::SubAssign<Vec2> for Vec2 {
+    fn sub_assign(&mut self, rhs: Vec2) {
+        *self = *self - rhs
     }
 }
 #[cfg(test)]
 mod tests {
-    use crate::model::pong::vectors::EuclideanVector;
+    use crate::model::pong::vectors::{EuclideanVector, Vec2};
+
+    fn close(a: Vec2, b: Vec2) -> bool {
+        (a - b).length() < 1e-9
+    }
 
     #[test]
     fn test_bed() {
@@ -160,9 +335,149 @@ mod tests {
         println!("{} {} | {} {}", v1.x_component(), v1.y_component(), norm_v1.x_component(), norm_v1.y_component())
     }
 
+    #[test]
+    fn reflect_off_floor_flips_vertical_component() {
+        let mut v = EuclideanVector::new(2.0, 45.0);
+        v.reflect(EuclideanVector::new(1.0, 270.0));
+        assert!((v.get_magnitude() - 2.0).abs() < 1e-9);
+        assert!((v.x_component() - 2.0_f64.sqrt()).abs() < 1e-9);
+        assert!((v.y_component() + 2.0_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reflect_ignores_normal_orientation_and_length() {
+        let v = EuclideanVector::new(3.0, 160.0);
+        let a = v.reflected(EuclideanVector::new(1.0, 0.0));
+        let b = v.reflected(EuclideanVector::new(7.0, 180.0));
+        assert!((a.x_component() - b.x_component()).abs() < 1e-9);
+        assert!((a.y_component() - b.y_component()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn vec2_converts_to_and_from_euclidean() {
+        let v = Vec2::new(3.0, -4.0);
+        let e = EuclideanVector::from(v);
+        assert!((e.get_magnitude() - 5.0).abs() < 1e-12);
+        assert!(close(Vec2::from(e), v));
+    }
+
+    #[test]
+    fn vec2_arithmetic() {
+        let (a, b) = (Vec2::new(1.0, 2.0), Vec2::new(3.0, -1.0));
+        assert_eq!(a + b, Vec2::new(4.0, 1.0));
+        assert_eq!(a - b, Vec2::new(-2.0, 3.0));
+        assert_eq!(-a, Vec2::new(-1.0, -2.0));
+        assert_eq!(a * 2.0, 2.0 * a);
+        assert_eq!(b / 2.0, Vec2::new(1.5, -0.5));
+        assert_eq!(a.dot(b), 1.0);
+        assert_eq!(a.cross(b), -7.0);
+        assert_eq!(a.perpendicular().dot(a), 0.0);
+        assert_eq!(a.lerp(b, 0.5), Vec2::new(2.0, 0.5));
+    }
+
+    #[test]
+    fn vec2_normalize_and_rotate() {
+        assert!((Vec2::new(3.0, 4.0).normalize().length() - 1.0).abs() < 1e-12);
+        assert_eq!(Vec2::ZERO.normalize(), Vec2::ZERO);
+        assert!(close(Vec2::new(1.0, 0.0).rotate(90.0), Vec2::new(0.0, 1.0)));
+        assert!(close(Vec2::from_angle(30.0).rotate(-30.0), Vec2::new(1.0, 0.0)));
+    }
+
+    #[test]
+    fn vec2_reflect_matches_euclidean_reflect() {
+        let v = EuclideanVector::new(2.5, 33.0);
+        let normal = EuclideanVector::new(1.0, 200.0);
+        let expected = Vec2::from(v.reflected(normal));
+        assert!(close(Vec2::from(v).reflect(Vec2::from(normal) * 3.0), expected));
+    }
+
     #[test]
     fn fn_new_control() {
         let new_vec = EuclideanVector::new(0.0, 0.0);
         assert_eq!(new_vec, EuclideanVector{magnitude: 0.0, angle: 0.0})
     }
+
+    #[test]
+    fn approx_eq_ignores_full_turns() {
+        let a = EuclideanVector::new(2.0, 45.0);
+        assert!(a.approx_eq(&EuclideanVector::new(2.0, -315.0), 1e-9));
+        assert!(a.approx_eq(&EuclideanVector::from_components(2.0_f64.sqrt(), 2.0_f64.sqrt()), 1e-9));
+        assert!(!a.approx_eq(&EuclideanVector::new(2.0, 46.0), 1e-9));
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use crate::model::pong::vectors::{EuclideanVector, Vec2};
+
+    // loose enough for a few rounds of trigonometry on magnitudes up to 1000
+    const EPS: f64 = 1e-6;
+
+    fn vector() -> impl Strategy<Value = EuclideanVector> {
+        (0.0..1000.0, -720.0..720.0).prop_map(|(magnitude, angle)| EuclideanVector::new(magnitude, angle))
+    }
+
+    fn normal() -> impl Strategy<Value = EuclideanVector> {
+        (0.1..10.0, -360.0..360.0).prop_map(|(magnitude, angle)| EuclideanVector::new(magnitude, angle))
+    }
+
+    proptest! {
+        #[test]
+        fn approx_eq_is_reflexive_and_symmetric(a in vector(), b in vector()) {
+            prop_assert!(a.approx_eq(&a, 0.0));
+            prop_assert_eq!(a.approx_eq(&b, EPS), b.approx_eq(&a, EPS));
+        }
+
+        #[test]
+        fn from_components_round_trips(v in vector()) {
+            let rebuilt = EuclideanVector::from_components(v.x_component(), v.y_component());
+            prop_assert!(rebuilt.approx_eq(&v, EPS));
+        }
+
+        #[test]
+        fn reflect_twice_is_identity(v in vector(), n in normal()) {
+            prop_assert!(v.reflected(n).reflected(n).approx_eq(&v, EPS));
+        }
+
+        #[test]
+        fn reflect_preserves_magnitude(v in vector(), n in normal()) {
+            prop_assert!((v.reflected(n).get_magnitude() - v.get_magnitude()).abs() <= EPS);
+        }
+
+        #[test]
+        fn reflect_flips_component_along_normal(v in vector(), n in normal()) {
+            let r = v.reflected(n);
+            prop_assert!((r * n + v * n).abs() <= EPS * n.get_magnitude());
+        }
+
+        #[test]
+        fn collide_with_matches_reflect(v in vector(), n in normal()) {
+            let mut collided = v;
+            collided.collide_with(n);
+            prop_assert!(collided.approx_eq(&v.reflected(n), EPS));
+        }
+
+        #[test]
+        fn addition_is_commutative(a in vector(), b in vector()) {
+            prop_assert!((a + b).approx_eq(&(b + a), EPS));
+        }
+
+        #[test]
+        fn subtraction_undoes_addition(a in vector(), b in vector()) {
+            prop_assert!(((a + b) - b).approx_eq(&a, EPS));
+        }
+
+        #[test]
+        fn dot_product_matches_components(a in vector(), b in vector()) {
+            let expected = a.x_component() * b.x_component() + a.y_component() * b.y_component();
+            prop_assert!((a * b - expected).abs() <= EPS * (1.0 + expected.abs()));
+        }
+
+        #[test]
+        fn vec2_round_trips(v in vector()) {
+            prop_assert!(EuclideanVector::from(Vec2::from(v)).approx_eq(&v, EPS));
+        }
+    }
 }
\ No newline at end of file
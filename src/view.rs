@@ -1,2 +1,3 @@
 pub mod gameview;
-pub mod assets;
\ No newline at end of file
+pub mod assets;
+pub mod input;
\ No newline at end of file
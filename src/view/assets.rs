@@ -155,7 +155,7 @@ pub mod color {
 }
 
 pub mod rectangle {
-    use fermium::{renderer::SDL_RenderFillRect, rect::SDL_Rect};
+    use fermium::{renderer::{SDL_RenderFillRect, SDL_RenderDrawRect}, rect::SDL_Rect};
     use super::{Drawable, drawable::Dimensions, drawable::{Point, HasColor, HasDimensions, HasPoint, HasZIndex}, color::RGBColor, color::{Colors}};
     
     // default paddle dimensions
@@ -169,7 +169,9 @@ pub mod rectangle {
         origin: Point,
         dims: Dimensions,
         color: RGBColor,
-        z_index: i32
+        z_index: i32,
+        // only the edges are drawn if not
+        filled: bool
     }
     
     impl Rectangle {
@@ -179,14 +181,19 @@ pub mod rectangle {
                 origin,
                 dims,
                 color,
-                z_index
+                z_index,
+                filled: true
             }
         }
+
+        pub fn outlined(id: usize, origin: Point, dims: Dimensions, color: RGBColor, z_index: i32) -> Rectangle {
+            Rectangle {filled: false, ..Rectangle::new(id, origin, dims, color, z_index)}
+        }
     }
 
     impl Default for Rectangle {
         fn default() -> Self {
-            Self {id: 0, origin: Point::new(0, 0), dims: Dimensions::new(0, 0), color: Colors::WHITE.as_rgb(), z_index: 0 }
+            Self {id: 0, origin: Point::new(0, 0), dims: Dimensions::new(0, 0), color: Colors::WHITE.as_rgb(), z_index: 0, filled: true }
         }
     }
 
@@ -196,15 +203,17 @@ pub mod rectangle {
         }
 
         unsafe fn draw(&self, renderer: *mut fermium::renderer::SDL_Renderer) -> bool {
-            SDL_RenderFillRect(
-                renderer,
-                &mut SDL_Rect{
-                    x: self.origin.x,
-                    y: self.origin.y,
-                    w: self.dims.w,
-                    h: self.dims.h
-                }
-            ) == 0
+            let rect = SDL_Rect{
+                x: self.origin.x,
+                y: self.origin.y,
+                w: self.dims.w,
+                h: self.dims.h
+            };
+            if self.filled {
+                SDL_RenderFillRect(renderer, &rect) == 0
+            } else {
+                SDL_RenderDrawRect(renderer, &rect) == 0
+            }
         }
     }
 
@@ -285,5 +294,728 @@ pub mod line {
             self.z_index
         }
     }
+
+    // a straight line drawn in dashes, dash pixels on and gap pixels off, starting with a dash
+    pub struct DashedLine {
+        id: usize,
+        from: Point,
+        to: Point,
+        dash: i32,
+        gap: i32,
+        color: RGBColor,
+        z_index: i32
+    }
+
+    impl DashedLine {
+        pub fn new(id: usize, from: Point, to: Point, dash: i32, gap: i32, color: RGBColor, z_index: i32) -> Self {
+            Self {id, from, to, dash: dash.max(1), gap: gap.max(0), color, z_index}
+        }
+
+        // where each dash starts and ends; the last is cut short at the end of the line
+        pub fn dashes(&self) -> Vec<(Point, Point)> {
+            let (dx, dy) = ((self.to.x - self.from.x) as f64, (self.to.y - self.from.y) as f64);
+            let length = (dx * dx + dy * dy).sqrt();
+            let along = |t: f64| Point::new(
+                self.from.x + (dx * t / length).round() as i32,
+                self.from.y + (dy * t / length).round() as i32
+            );
+            let mut dashes = Vec::new();
+            let mut t = 0.0;
+            while t < length {
+                dashes.push((along(t), along((t + self.dash as f64).min(length))));
+                t += (self.dash + self.gap) as f64;
+            }
+            dashes
+        }
+    }
+
+    impl Drawable for DashedLine {
+        fn fetch_id(&self) -> usize {
+            self.id
+        }
+
+        unsafe fn draw(&self, renderer: *mut fermium::renderer::SDL_Renderer) -> bool {
+            self.dashes().into_iter().all(|(p1, p2)| SDL_RenderDrawLine(renderer, p1.x, p1.y, p2.x, p2.y) == 0)
+        }
+    }
+
+    impl HasColor for DashedLine {
+        fn fetch_color(&self) -> RGBColor {
+            self.color
+        }
+    }
+
+    impl HasPoint for DashedLine {
+        fn fetch_origin(&self) -> Point {
+            self.from
+        }
+    }
+
+    impl HasZIndex for DashedLine {
+        fn fetch_z_index(&self) -> i32 {
+            self.z_index
+        }
+    }
+}
+
+pub mod circle {
+    use fermium::{renderer::{SDL_RenderFillRects, SDL_RenderDrawPoints}, rect::{SDL_Rect, SDL_Point}};
+
+    use super::{Drawable, drawable::{Dimensions, HasColor, HasDimensions, HasPoint, HasZIndex, Point}, color::RGBColor};
+
+    pub struct Circle {
+        id: usize,
+        center: Point,
+        radius: i32,
+        color: RGBColor,
+        z_index: i32,
+        // only the rim is drawn if not
+        filled: bool
+    }
+
+    impl Circle {
+        pub fn new(id: usize, center: Point, radius: i32, color: RGBColor, z_index: i32) -> Self {
+            Self {id, center, radius: radius.max(0), color, z_index, filled: true}
+        }
+
+        pub fn outlined(id: usize, center: Point, radius: i32, color: RGBColor, z_index: i32) -> Self {
+            Self {filled: false, ..Self::new(id, center, radius, color, z_index)}
+        }
+
+        // the largest circle that fits in a box, which is how game objects are sized
+        pub fn in_box(id: usize, origin: Point, dims: Dimensions, color: RGBColor, z_index: i32) -> Self {
+            let center = Point::new(origin.x + dims.w / 2, origin.y + dims.h / 2);
+            Self::new(id, center, (dims.w.min(dims.h) - 1) / 2, color, z_index)
+        }
+
+        pub fn get_center(&self) -> Point {
+            self.center
+        }
+
+        pub fn get_radius(&self) -> i32 {
+            self.radius
+        }
+
+        // one row of the filled disc per pixel of height, as (x, y, w, h)
+        pub fn spans(&self) -> Vec<(i32, i32, i32, i32)> {
+            let r = self.radius;
+            (-r..=r).map(|dy| {
+                let half = ((r * r - dy * dy) as f64).sqrt() as i32;
+                (self.center.x - half, self.center.y + dy, 2 * half + 1, 1)
+            }).collect()
+        }
+
+        // the rim, found an eighth at a time with the midpoint algorithm and mirrored round
+        pub fn rim(&self) -> Vec<Point> {
+            let (cx, cy) = (self.center.x, self.center.y);
+            let (mut x, mut y, mut error) = (self.radius, 0, 1 - self.radius);
+            let mut points = Vec::new();
+            while x >= y {
+                for (px, py) in [(x, y), (y, x), (-y, x), (-x, y), (-x, -y), (-y, -x), (y, -x), (x, -y)] {
+                    points.push(Point::new(cx + px, cy + py));
+                }
+                y += 1;
+                if error < 0 {
+                    error += 2 * y + 1;
+                } else {
+                    x -= 1;
+                    error += 2 * (y - x) + 1;
+                }
+            }
+            points
+        }
+    }
+
+    impl Drawable for Circle {
+        fn fetch_id(&self) -> usize {
+            self.id
+        }
+
+        unsafe fn draw(&self, renderer: *mut fermium::renderer::SDL_Renderer) -> bool {
+            if self.filled {
+                let rects: Vec<SDL_Rect> = self.spans().into_iter().map(|(x, y, w, h)| SDL_Rect {x, y, w, h}).collect();
+                SDL_RenderFillRects(renderer, rects.as_ptr(), rects.len() as i32) == 0
+            } else {
+                let points: Vec<SDL_Point> = self.rim().into_iter().map(|p| SDL_Point {x: p.x, y: p.y}).collect();
+                SDL_RenderDrawPoints(renderer, points.as_ptr(), points.len() as i32) == 0
+            }
+        }
+    }
+
+    impl HasColor for Circle {
+        fn fetch_color(&self) -> RGBColor {
+            self.color
+        }
+    }
+
+    impl HasDimensions for Circle {
+        fn fetch_dimensions(&self) -> Dimensions {
+            Dimensions::new(2 * self.radius + 1, 2 * self.radius + 1)
+        }
+    }
+
+    // the top left of the circle's box, as for every other drawable
+    impl HasPoint for Circle {
+        fn fetch_origin(&self) -> Point {
+            Point::new(self.center.x - self.radius, self.center.y - self.radius)
+        }
+    }
+
+    impl HasZIndex for Circle {
+        fn fetch_z_index(&self) -> i32 {
+            self.z_index
+        }
+    }
+}
+
+pub mod polygon {
+    use fermium::{renderer::SDL_RenderFillRects, rect::SDL_Rect};
+
+    use super::{Drawable, drawable::{HasColor, HasPoint, HasZIndex, Point}, color::RGBColor};
+
+    // a closed shape through the points in order, back to the first
+    pub struct Polygon {
+        id: usize,
+        points: Vec<Point>,
+        color: RGBColor,
+        z_index: i32
+    }
+
+    impl Polygon {
+        pub fn new(id: usize, points: Vec<Point>, color: RGBColor, z_index: i32) -> Self {
+            Self {id, points, color, z_index}
+        }
+
+        /*
+            Filling.
+                Each row of pixels is crossed at its middle and filled between every other crossing,
+                the even-odd rule, so a shape that crosses itself leaves holes where it overlaps.
+         */
+        pub fn spans(&self) -> Vec<(i32, i32, i32, i32)> {
+            let (Some(top), Some(bottom)) = (self.points.iter().map(|p| p.y).min(), self.points.iter().map(|p| p.y).max()) else {
+                return Vec::new()
+            };
+            let edges: Vec<(Point, Point)> = self.points.iter().zip(self.points.iter().cycle().skip(1)).map(|(a, b)| (*a, *b)).collect();
+            let mut spans = Vec::new();
+            for y in top..bottom {
+                let row = y as f64 + 0.5;
+                let mut crossings: Vec<f64> = edges.iter()
+                    .filter(|(a, b)| (a.y as f64 <= row) != (b.y as f64 <= row))
+                    .map(|(a, b)| a.x as f64 + (row - a.y as f64) * (b.x - a.x) as f64 / (b.y - a.y) as f64)
+                    .collect();
+                crossings.sort_by(f64::total_cmp);
+                for pair in crossings.chunks_exact(2) {
+                    let (from, to) = (pair[0].round() as i32, pair[1].round() as i32);
+                    if to > from {
+                        spans.push((from, y, to - from, 1));
+                    }
+                }
+            }
+            spans
+        }
+    }
+
+    impl Drawable for Polygon {
+        fn fetch_id(&self) -> usize {
+            self.id
+        }
+
+        unsafe fn draw(&self, renderer: *mut fermium::renderer::SDL_Renderer) -> bool {
+            let rects: Vec<SDL_Rect> = self.spans().into_iter().map(|(x, y, w, h)| SDL_Rect {x, y, w, h}).collect();
+            SDL_RenderFillRects(renderer, rects.as_ptr(), rects.len() as i32) == 0
+        }
+    }
+
+    impl HasColor for Polygon {
+        fn fetch_color(&self) -> RGBColor {
+            self.color
+        }
+    }
+
+    impl HasPoint for Polygon {
+        fn fetch_origin(&self) -> Point {
+            self.points.first().copied().unwrap_or(Point::new(0, 0))
+        }
+    }
+
+    impl HasZIndex for Polygon {
+        fn fetch_z_index(&self) -> i32 {
+            self.z_index
+        }
+    }
+}
+
+/*
+    Render styles.
+        How a kind of game object is drawn, picked per object type by whoever turns the model into
+        drawings: the shape filling the object's box, its color and its z-index.
+ */
+pub mod style {
+    use serde::Deserialize;
+
+    use super::{Drawable, drawable::{Dimensions, Point}, color::RGBColor, rectangle::Rectangle, circle::Circle};
+
+    // named in config files in lowercase, e.g. "outlined_circle"
+    #[allow(non_camel_case_types)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum Shape {
+        RECTANGLE,
+        OUTLINED_RECTANGLE,
+        // the largest that fits in the box
+        CIRCLE,
+        OUTLINED_CIRCLE
+    }
+
+    #[derive(Clone, Copy)]
+    pub struct RenderStyle {
+        pub shape: Shape,
+        pub color: RGBColor,
+        pub z_index: i32
+    }
+
+    impl RenderStyle {
+        pub fn new(shape: Shape, color: RGBColor, z_index: i32) -> Self {
+            Self {shape, color, z_index}
+        }
+
+        // the drawing of something in this style taking up the box at origin
+        pub fn drawing(&self, id: usize, origin: Point, dims: Dimensions) -> Box<dyn Drawable> {
+            match self.shape {
+                Shape::RECTANGLE            => Box::new(Rectangle::new(id, origin, dims, self.color, self.z_index)),
+                Shape::OUTLINED_RECTANGLE   => Box::new(Rectangle::outlined(id, origin, dims, self.color, self.z_index)),
+                Shape::CIRCLE               => Box::new(Circle::in_box(id, origin, dims, self.color, self.z_index)),
+                Shape::OUTLINED_CIRCLE      => {
+                    let circle = Circle::in_box(id, origin, dims, self.color, self.z_index);
+                    Box::new(Circle::outlined(id, circle.get_center(), circle.get_radius(), self.color, self.z_index))
+                }
+            }
+        }
+    }
+}
+
+
+/*
+    Seven-segment digits.
+        Numbers and short strings drawn the way a scoreboard does, from up to seven bars per
+        character and no font. The segments are named the usual way, as bits of a mask:
+
+             aaa
+            f   b
+             ggg
+            e   c
+             ddd
+
+        Digits, a space, a dash and the letters a seven-segment display can manage are supported;
+        anything else is drawn as a blank.
+ */
+pub mod digits {
+    use fermium::{renderer::SDL_RenderFillRect, rect::SDL_Rect};
+
+    use super::{Drawable, drawable::{Dimensions, HasColor, HasDimensions, HasPoint, HasZIndex, Point}, color::RGBColor};
+
+    const A: u8 = 1;
+    const B: u8 = 1 << 1;
+    const C: u8 = 1 << 2;
+    const D: u8 = 1 << 3;
+    const E: u8 = 1 << 4;
+    const F: u8 = 1 << 5;
+    const G: u8 = 1 << 6;
+
+    pub struct SevenSegmentDigits {
+        id: usize,
+        origin: Point,
+        text: String,
+        // the size of a single character; the bars are thickness wide
+        glyph: Dimensions,
+        thickness: i32,
+        color: RGBColor,
+        z_index: i32
+    }
+
+    impl SevenSegmentDigits {
+        pub fn new(id: usize, origin: Point, text: impl Into<String>, glyph: Dimensions, thickness: i32, color: RGBColor, z_index: i32) -> Self {
+            Self {id, origin, text: text.into(), glyph, thickness, color, z_index}
+        }
+
+        // which segments light up for c
+        pub fn segments(c: char) -> u8 {
+            match c.to_ascii_lowercase() {
+                '0'         => A | B | C | D | E | F,
+                '1'         => B | C,
+                '2' | 'z'   => A | B | G | E | D,
+                '3'         => A | B | G | C | D,
+                '4'         => F | G | B | C,
+                '5' | 's'   => A | F | G | C | D,
+                '6' | 'g'   => A | F | G | E | D | C,
+                '7'         => A | B | C,
+                '8'         => A | B | C | D | E | F | G,
+                '9'         => A | B | C | D | F | G,
+                'a'         => A | B | C | E | F | G,
+                'b'         => F | E | D | C | G,
+                'c'         => A | F | E | D,
+                'd'         => B | C | D | E | G,
+                'e'         => A | F | G | E | D,
+                'f'         => A | F | G | E,
+                'h'         => F | E | G | B | C,
+                'i'         => E | F,
+                'j'         => B | C | D | E,
+                'l'         => F | E | D,
+                'n'         => E | G | C,
+                'o'         => E | G | C | D,
+                'p'         => A | B | F | G | E,
+                'r'         => E | G,
+                't'         => F | E | D | G,
+                'u'         => F | E | D | C | B,
+                'y'         => F | G | B | C | D,
+                '-'         => G,
+                _           => 0
+            }
+        }
+
+        // moves the text so that its middle, rather than its left edge, sits at the origin
+        pub fn centred(mut self) -> Self {
+            self.origin.x -= self.width() / 2;
+            self
+        }
+
+        // width of the whole text, with a bar's width between characters
+        pub fn width(&self) -> i32 {
+            let count = self.text.chars().count() as i32;
+            (count * (self.glyph.w + self.thickness) - self.thickness).max(0)
+        }
+
+        // the bars to fill, as (x, y, w, h)
+        pub fn bars(&self) -> Vec<(i32, i32, i32, i32)> {
+            let (w, h, t) = (self.glyph.w, self.glyph.h, self.thickness);
+            let middle = (h - t) / 2;
+            let mut bars = Vec::new();
+            for (i, c) in self.text.chars().enumerate() {
+                let x = self.origin.x + i as i32 * (w + t);
+                let y = self.origin.y;
+                let segments = Self::segments(c);
+                let shapes = [
+                    (A, (x, y, w, t)),
+                    (B, (x + w - t, y, t, middle + t)),
+                    (C, (x + w - t, y + middle, t, h - middle)),
+                    (D, (x, y + h - t, w, t)),
+                    (E, (x, y + middle, t, h - middle)),
+                    (F, (x, y, t, middle + t)),
+                    (G, (x, y + middle, w, t))
+                ];
+                bars.extend(shapes.iter().filter(|(segment, _)| segments & segment != 0).map(|(_, bar)| *bar));
+            }
+            bars
+        }
+    }
+
+    impl Drawable for SevenSegmentDigits {
+        fn fetch_id(&self) -> usize {
+            self.id
+        }
+
+        unsafe fn draw(&self, renderer: *mut fermium::renderer::SDL_Renderer) -> bool {
+            self.bars().into_iter().all(|(x, y, w, h)| SDL_RenderFillRect(renderer, &SDL_Rect {x, y, w, h}) == 0)
+        }
+    }
+
+    impl HasColor for SevenSegmentDigits {
+        fn fetch_color(&self) -> RGBColor {
+            self.color
+        }
+    }
+
+    impl HasDimensions for SevenSegmentDigits {
+        fn fetch_dimensions(&self) -> Dimensions {
+            Dimensions::new(self.width(), self.glyph.h)
+        }
+    }
+
+    impl HasPoint for SevenSegmentDigits {
+        fn fetch_origin(&self) -> Point {
+            self.origin
+        }
+    }
+
+    impl HasZIndex for SevenSegmentDigits {
+        fn fetch_z_index(&self) -> i32 {
+            self.z_index
+        }
+    }
 }
 
+/*
+    Text.
+        Menus, labels and overlays in a small bitmap font built into the game, so there are no font
+        files to ship and nothing beyond SDL to link against. Each character is five pixels wide and
+        seven tall, scaled up to the requested size and drawn a row of lit pixels at a time.
+        Letters are drawn as capitals, characters the font lacks as blanks, and a newline starts
+        another line under the first.
+ */
+pub mod text {
+    use fermium::{renderer::SDL_RenderFillRect, rect::SDL_Rect};
+
+    use super::{Drawable, drawable::{Dimensions, HasColor, HasDimensions, HasPoint, HasZIndex, Point}, color::RGBColor};
+
+    // which part of each line sits at the origin's x
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum Align {
+        #[default]
+        LEFT,
+        CENTER,
+        RIGHT
+    }
+
+    pub struct Text {
+        id: usize,
+        origin: Point,
+        text: String,
+        // the height of a capital asked for, in pixels; see new
+        size: i32,
+        align: Align,
+        color: RGBColor,
+        z_index: i32
+    }
+
+    impl Text {
+        pub const GLYPH_WIDTH: i32 = 5;
+        pub const GLYPH_HEIGHT: i32 = 7;
+        // a column between characters, two rows between lines
+        const ADVANCE: i32 = Self::GLYPH_WIDTH + 1;
+        const LINE_HEIGHT: i32 = Self::GLYPH_HEIGHT + 2;
+
+        // size is rounded down to a multiple of GLYPH_HEIGHT, at least GLYPH_HEIGHT, so every font pixel is a whole
+        // square: 16 draws capitals 14 pixels high
+        pub fn new(id: usize, origin: Point, text: impl Into<String>, size: i32, align: Align, color: RGBColor, z_index: i32) -> Self {
+            Self {id, origin, text: text.into(), size, align, color, z_index}
+        }
+
+        // how many pixels each font pixel is drawn as
+        pub fn scale(&self) -> i32 {
+            (self.size / Self::GLYPH_HEIGHT).max(1)
+        }
+
+        // the rows of c from the top, the leftmost pixel in the highest of the five bits
+        pub fn glyph(c: char) -> [u8; 7] {
+            match c.to_ascii_uppercase() {
+                '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+                '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+                '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+                '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+                '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+                '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+                '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+                '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+                '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+                '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+                'A' => [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11],
+                'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+                'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+                'D' => [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C],
+                'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+                'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+                'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+                'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+                'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+                'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+                'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+                'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+                'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+                'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+                'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+                'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+                'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+                'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+                'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+                'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+                'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+                'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+                'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+                'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+                'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+                'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+                '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+                ',' => [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08],
+                ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+                '!' => [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04],
+                '?' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04],
+                '\'' => [0x0C, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00],
+                '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+                '+' => [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00],
+                '=' => [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00],
+                '/' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00],
+                '%' => [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03],
+                '(' => [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02],
+                ')' => [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08],
+                '_' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F],
+                _   => [0x00; 7]
+            }
+        }
+
+        fn line_width(&self, line: &str) -> i32 {
+            (line.chars().count() as i32 * Self::ADVANCE - 1).max(0) * self.scale()
+        }
+
+        // the widest line's width, and the height from the top of the first line to the bottom of the last
+        pub fn extent(&self) -> (i32, i32) {
+            let lines = self.text.lines().count().max(1) as i32;
+            let width = self.text.lines().map(|line| self.line_width(line)).max().unwrap_or(0);
+            (width, ((lines - 1) * Self::LINE_HEIGHT + Self::GLYPH_HEIGHT) * self.scale())
+        }
+
+        // the runs of lit pixels to fill, as (x, y, w, h)
+        pub fn runs(&self) -> Vec<(i32, i32, i32, i32)> {
+            let scale = self.scale();
+            let mut runs = Vec::new();
+            for (row, line) in self.text.lines().enumerate() {
+                let left = match self.align {
+                    Align::LEFT => self.origin.x,
+                    Align::CENTER => self.origin.x - self.line_width(line) / 2,
+                    Align::RIGHT => self.origin.x - self.line_width(line)
+                };
+                let top = self.origin.y + row as i32 * Self::LINE_HEIGHT * scale;
+                for (column, c) in line.chars().enumerate() {
+                    let x = left + column as i32 * Self::ADVANCE * scale;
+                    for (i, bits) in Self::glyph(c).iter().enumerate() {
+                        let y = top + i as i32 * scale;
+                        let mut start = None;
+                        // one past the last column, so that a run reaching the right edge is closed
+                        for bit in 0..=Self::GLYPH_WIDTH {
+                            let lit = bit < Self::GLYPH_WIDTH && bits & (0x10 >> bit) != 0;
+                            match (lit, start) {
+                                (true, None) => start = Some(bit),
+                                (false, Some(from)) => {
+                                    runs.push((x + from * scale, y, (bit - from) * scale, scale));
+                                    start = None
+                                },
+                                _ => ()
+                            }
+                        }
+                    }
+                }
+            }
+            runs
+        }
+    }
+
+    impl Drawable for Text {
+        fn fetch_id(&self) -> usize {
+            self.id
+        }
+
+        unsafe fn draw(&self, renderer: *mut fermium::renderer::SDL_Renderer) -> bool {
+            self.runs().into_iter().all(|(x, y, w, h)| SDL_RenderFillRect(renderer, &SDL_Rect {x, y, w, h}) == 0)
+        }
+    }
+
+    impl HasColor for Text {
+        fn fetch_color(&self) -> RGBColor {
+            self.color
+        }
+    }
+
+    impl HasDimensions for Text {
+        fn fetch_dimensions(&self) -> Dimensions {
+            let (w, h) = self.extent();
+            Dimensions::new(w, h)
+        }
+    }
+
+    impl HasPoint for Text {
+        fn fetch_origin(&self) -> Point {
+            self.origin
+        }
+    }
+
+    impl HasZIndex for Text {
+        fn fetch_z_index(&self) -> i32 {
+            self.z_index
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{digits::SevenSegmentDigits, text::{Text, Align}, line::DashedLine, circle::Circle, polygon::Polygon, drawable::{Dimensions, Point}, color::Colors};
+
+    fn digits(text: &str) -> SevenSegmentDigits {
+        SevenSegmentDigits::new(0, Point::new(10, 20), text, Dimensions::new(20, 40), 4, Colors::WHITE.as_rgb(), 0)
+    }
+
+    #[test]
+    fn digits_light_the_usual_segments() {
+        let lit = |c| SevenSegmentDigits::segments(c).count_ones();
+        assert_eq!((0..=9).map(|d| lit(char::from_digit(d, 10).unwrap())).collect::<Vec<u32>>(), vec![6, 2, 5, 5, 4, 5, 6, 3, 7, 6]);
+        assert_eq!(lit('?'), 0);
+    }
+
+    #[test]
+    fn characters_are_laid_out_left_to_right() {
+        let text = digits("10");
+        assert_eq!(text.width(), 2 * 20 + 4);
+        let bars = text.bars();
+        assert_eq!(bars.len(), 2 + 6);
+        // the 1 is the right-hand bars of the first cell, the 0 starts a bar's width after it
+        assert!(bars[..2].iter().all(|(x, _, w, _)| x + w == 30));
+        assert!(bars[2..].iter().all(|(x, _, _, _)| *x >= 34));
+    }
+
+    #[test]
+    fn text_fills_each_run_of_lit_pixels_once() {
+        // the T's bar is one run, its stem one per row below
+        let text = Text::new(0, Point::new(0, 0), "t", 14, Align::LEFT, Colors::WHITE.as_rgb(), 0);
+        let runs = text.runs();
+        assert_eq!(runs.len(), 7);
+        assert_eq!(runs[0], (0, 0, 10, 2));
+        assert!(runs[1..].iter().all(|(x, _, w, _)| (*x, *w) == (4, 2)));
+        assert_eq!(text.extent(), (10, 14));
+        // sizes between whole scales round down, and nothing is drawn smaller than the font itself
+        assert_eq!(Text::new(0, Point::new(0, 0), "t", 16, Align::LEFT, Colors::WHITE.as_rgb(), 0).extent(), (10, 14));
+        assert_eq!(Text::new(0, Point::new(0, 0), "t", 3, Align::LEFT, Colors::WHITE.as_rgb(), 0).extent(), (5, 7));
+    }
+
+    #[test]
+    fn text_lines_are_aligned_on_the_origin() {
+        let runs = |align| Text::new(0, Point::new(100, 0), "ab\nc", 7, align, Colors::WHITE.as_rgb(), 0).runs();
+        let span = |runs: &[(i32, i32, i32, i32)], line: i32| {
+            let on_line = runs.iter().filter(|(_, y, _, _)| y / 9 == line);
+            (on_line.clone().map(|r| r.0).min().unwrap(), on_line.map(|r| r.0 + r.2).max().unwrap())
+        };
+        assert_eq!(span(&runs(Align::LEFT), 0), (100, 111));
+        assert_eq!(span(&runs(Align::RIGHT), 0), (89, 100));
+        assert_eq!(span(&runs(Align::CENTER), 1), (98, 103));
+        assert!(Text::new(0, Point::new(0, 0), "~", 7, Align::LEFT, Colors::WHITE.as_rgb(), 0).runs().is_empty());
+    }
+
+    #[test]
+    fn circles_stay_within_their_radius() {
+        let circle = Circle::new(0, Point::new(50, 50), 10, Colors::WHITE.as_rgb(), 0);
+        let spans = circle.spans();
+        assert_eq!(spans.len(), 21);
+        assert_eq!(spans[10], (40, 50, 21, 1));
+        assert!(spans.iter().all(|(x, _, w, _)| *x >= 40 && x + w <= 61));
+        let within = |p: &Point| ((p.x - 50).pow(2) + (p.y - 50).pow(2)) as f64;
+        assert!(circle.rim().iter().all(|p| (within(p).sqrt() - 10.0).abs() < 1.0));
+        // a box fits the widest circle it can hold, an odd number of pixels across
+        let ball = Circle::in_box(0, Point::new(0, 0), Dimensions::new(10, 10), Colors::WHITE.as_rgb(), 0);
+        assert_eq!((ball.get_center().x, ball.get_center().y, ball.get_radius()), (5, 5, 4));
+    }
+
+    #[test]
+    fn polygons_fill_between_their_edges() {
+        let square = Polygon::new(0, vec![Point::new(0, 0), Point::new(10, 0), Point::new(10, 10), Point::new(0, 10)], Colors::WHITE.as_rgb(), 0);
+        assert_eq!(square.spans(), (0..10).map(|y| (0, y, 10, 1)).collect::<Vec<_>>());
+        let triangle = Polygon::new(0, vec![Point::new(0, 0), Point::new(20, 10), Point::new(0, 20)], Colors::WHITE.as_rgb(), 0);
+        let widths: Vec<i32> = triangle.spans().iter().map(|(_, _, w, _)| *w).collect();
+        assert_eq!(widths.len(), 20);
+        assert!(widths[..10].windows(2).all(|w| w[0] <= w[1]) && widths[10..].windows(2).all(|w| w[0] >= w[1]));
+        assert!(Polygon::new(0, Vec::new(), Colors::WHITE.as_rgb(), 0).spans().is_empty());
+    }
+
+    #[test]
+    fn dashed_lines_alternate_and_stop_at_the_end() {
+        let net = DashedLine::new(0, Point::new(5, 0), Point::new(5, 50), 10, 5, Colors::WHITE.as_rgb(), 0);
+        let ends: Vec<(i32, i32)> = net.dashes().iter().map(|(from, to)| (from.y, to.y)).collect();
+        assert_eq!(ends, vec![(0, 10), (15, 25), (30, 40), (45, 50)]);
+    }
+}
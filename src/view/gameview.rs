@@ -4,7 +4,7 @@ use fermium::{
     *
 };
 use priority_queue::PriorityQueue;
-use crate::view::assets::Drawable;
+use crate::view::{assets::Drawable, input::InputEvent};
 
 use self::gfx_environments::{IsGraphicsEnvironment, IsWindow, IsRenderer, sdl2::{SDLWindow, SDLGraphicsEnvironment, SDLRenderer, options::WindowOptions}, Environments, invalid::InvalidWindow};
 
@@ -19,7 +19,8 @@ pub struct GameView {
     started: bool,
     gfx_env: Environments,
     width: i32,
-    height: i32
+    height: i32,
+    fullscreen: bool
 }
 
 // Constructors
@@ -29,7 +30,7 @@ impl GameView {
         env.init();
         let window = SDLWindow::from_dims(width, height);
         let renderer = SDLRenderer::default(window.expose_window());
-        Self {env, window, renderer, drawings: PriorityQueue::new(), started: false, gfx_env, width, height}
+        Self {env, window, renderer, drawings: PriorityQueue::new(), started: false, gfx_env, width, height, fullscreen: false}
     }
 
     pub unsafe fn default() -> Self {
@@ -43,6 +44,20 @@ impl GameView {
     pub unsafe fn sdl2(width: i32, height: i32) -> Self {
         Self::new(Environments::SDL, width, height)
     }
+
+    /// Covers the desktop, scaling a width x height drawing surface to fit it.
+    ///
+    /// # Safety
+    ///
+    /// Initialises SDL video and creates a window and renderer; call it from the main thread only.
+    pub unsafe fn sdl2_fullscreen(width: i32, height: i32) -> Self {
+        let env = SDLGraphicsEnvironment::new(10);
+        env.init();
+        let window = SDLWindow::fullscreen(width, height);
+        let renderer = SDLRenderer::default(window.expose_window());
+        renderer.set_logical_size(width, height);
+        Self {env, window, renderer, drawings: PriorityQueue::new(), started: false, gfx_env: Environments::SDL, width, height, fullscreen: true}
+    }
 }
 
 
@@ -78,16 +93,23 @@ impl GameView {
         if !self.started {
             self.init();
         }
-        self.window = SDLWindow::from_dims(self.width, self.height);
-        self.renderer = SDLRenderer::default(self.window.expose_window())
+        if self.fullscreen {
+            self.window = SDLWindow::fullscreen(self.width, self.height);
+            self.renderer = SDLRenderer::default(self.window.expose_window());
+            self.renderer.set_logical_size(self.width, self.height)
+        } else {
+            self.window = SDLWindow::from_dims(self.width, self.height);
+            self.renderer = SDLRenderer::default(self.window.expose_window())
+        }
     }
 
     pub unsafe fn get_screen_dimensions(&self) -> (i32, i32) {
         self.window.fetch_dimensions()
     }
 
-    pub unsafe fn keyboard_input(&mut self) -> Option<i32> {
-        self.env.handle_keyboard_input()
+    // every input event that arrived since the last call, oldest first
+    pub unsafe fn input_events(&mut self) -> Vec<InputEvent> {
+        self.env.poll_input()
     }
 
     pub unsafe fn render(&mut self) {
@@ -115,7 +137,7 @@ impl GameView {
 
 pub mod gfx_environments {
     use std::any::Any;
-    use crate::view::assets::{color::RGBColor, Drawable};
+    use crate::view::{assets::{color::RGBColor, Drawable}, input::InputEvent};
 
     use self::{invalid::{InvalidGraphicsEnvironment, InvalidWindow, InvalidRenderer}, sdl2::{SDLGraphicsEnvironment, SDLWindow, SDLRenderer}};
     
@@ -158,7 +180,7 @@ pub mod gfx_environments {
         unsafe fn init(&self);
         unsafe fn quit(&self);
         unsafe fn delay(&self);
-        unsafe fn handle_keyboard_input(&mut self) -> Option<i32>;
+        unsafe fn poll_input(&mut self) -> Vec<InputEvent>;
     }
 
     pub trait IsWindow {
@@ -178,7 +200,7 @@ pub mod gfx_environments {
             use std::any::Any;
 
             use crate::view::gameview::gfx_environments::{IsGraphicsEnvironment, IsWindow, IsRenderer};
-            use crate::view::assets::{color::RGBColor, Drawable};
+            use crate::view::{assets::{color::RGBColor, Drawable}, input::InputEvent};
 
             pub struct InvalidGraphicsEnvironment;
 
@@ -195,7 +217,7 @@ pub mod gfx_environments {
                     panic!("The graphics environment is invalid!");
                 }
 
-                unsafe fn handle_keyboard_input(&mut self) -> Option<i32> {
+                unsafe fn poll_input(&mut self) -> Vec<InputEvent> {
                     panic!("The graphics environment is invalid!");
                 }
             }
@@ -257,10 +279,10 @@ pub mod gfx_environments {
     
     pub mod sdl2 {
         
-        use std::any::Any;
+        use std::{any::Any, collections::HashMap};
 
-        use fermium::{SDL_Init, SDL_INIT_EVERYTHING, SDL_Quit, timer::SDL_Delay, video::{SDL_Window, SDL_GetWindowSize, SDL_DestroyWindow, SDL_CreateWindow, SDL_WINDOWPOS_CENTERED}, renderer::{SDL_Renderer, SDL_CreateRenderer, SDL_RenderClear, SDL_RenderPresent, SDL_SetRenderDrawColor}, prelude::{SDL_Event, SDL_WaitEventTimeout, SDL_KEYDOWN, SDL_KEYUP}};
-        use crate::view::gameview::{gfx_environments::IsGraphicsEnvironment};
+        use fermium::{gamecontroller::{SDL_GameController, SDL_GameControllerOpen, SDL_GameControllerClose, SDL_GameControllerGetJoystick}, joystick::SDL_JoystickInstanceID, SDL_Init, SDL_INIT_EVERYTHING, SDL_Quit, timer::SDL_Delay, video::{SDL_Window, SDL_GetWindowSize, SDL_DestroyWindow, SDL_CreateWindow, SDL_WINDOWPOS_CENTERED, SDL_WINDOW_FULLSCREEN_DESKTOP}, renderer::{SDL_Renderer, SDL_CreateRenderer, SDL_RenderSetLogicalSize, SDL_RenderClear, SDL_RenderPresent, SDL_SetRenderDrawColor}, prelude::{SDL_Event, SDL_PollEvent}};
+        use crate::view::{gameview::gfx_environments::IsGraphicsEnvironment, input::{self, InputEvent}};
 
         use self::options::{WindowOptions, RendererOptions};
         use super::{IsWindow, IsRenderer};
@@ -330,7 +352,8 @@ pub mod gfx_environments {
         pub struct SDLGraphicsEnvironment {
             ms_delay: u32,
             event: SDL_Event,
-            timeout: i32
+            // open game controllers by instance id
+            pads: HashMap<i32, *mut SDL_GameController>
         }
 
         impl SDLGraphicsEnvironment {
@@ -351,30 +374,44 @@ pub mod gfx_environments {
                 SDL_Delay(self.ms_delay)
             }
 
-            unsafe fn handle_keyboard_input(&mut self) -> Option<i32> {
-                if SDL_WaitEventTimeout(&mut self.event, self.timeout) ==  Self::SDL_EVENT_SUCCESS {
-                    match self.event.type_ {
-                        SDL_KEYDOWN => {
-                            Option::Some(self.event.key.keysym.sym.0)
-                        },
-                        SDL_KEYUP => {
-                            Option::Some(-1)
-                        },
-                        _ => Option::None
-                    }
-                } else {
-                    Option::None
+            unsafe fn poll_input(&mut self) -> Vec<InputEvent> {
+                let mut events = Vec::new();
+                while SDL_PollEvent(&mut self.event) == Self::SDL_EVENT_SUCCESS {
+                    let event = match input::translate(&self.event) {
+                        Some(InputEvent::PADADDED(index)) => self.open_pad(index),
+                        Some(InputEvent::PADREMOVED(id)) => self.close_pad(id),
+                        event => event
+                    };
+                    events.extend(event);
                 }
+                events
             }
         }
 
         impl SDLGraphicsEnvironment {
             pub const DEFAULT_REFRESH_RATE: u32 = 10;
+
+            // a pad has to be opened before SDL reports its buttons and axes
+            unsafe fn open_pad(&mut self, index: i32) -> Option<InputEvent> {
+                let pad = SDL_GameControllerOpen(index);
+                if pad.is_null() {
+                    return None
+                }
+                let id = SDL_JoystickInstanceID(SDL_GameControllerGetJoystick(pad)).0;
+                self.pads.insert(id, pad);
+                Some(InputEvent::PADADDED(id))
+            }
+
+            unsafe fn close_pad(&mut self, id: i32) -> Option<InputEvent> {
+                let pad = self.pads.remove(&id)?;
+                SDL_GameControllerClose(pad);
+                Some(InputEvent::PADREMOVED(id))
+            }
         }
 
         impl SDLGraphicsEnvironment {
             pub fn new(ms_delay: u32) -> Self {
-                Self{ms_delay, event: SDL_Event::default(), timeout: 1}
+                Self{ms_delay, event: SDL_Event::default(), pads: HashMap::new()}
             }
         }
 
@@ -438,6 +475,15 @@ pub mod gfx_environments {
                 options.h = height;
                 Self::new(options)
             }
+
+            /// # Safety
+            ///
+            /// SDL video must already be initialised, and the window is only to be used from the thread
+            /// that created it.
+            pub unsafe fn fullscreen(width: i32, height: i32) -> Self {
+                let defaults = WindowOptions::default();
+                Self::new(WindowOptions {w: width, h: height, flags: defaults.flags | SDL_WINDOW_FULLSCREEN_DESKTOP.0, ..defaults})
+            }
         }
 
         pub struct SDLRenderer {
@@ -475,6 +521,15 @@ pub mod gfx_environments {
             pub unsafe fn default(window: *mut SDL_Window) -> Self {
                 Self::new(window, RendererOptions::default())
             }
+
+            /// Draw in width x height coordinates regardless of the window's real size.
+            ///
+            /// # Safety
+            ///
+            /// The renderer must still be alive, i.e. its window not yet destroyed.
+            pub unsafe fn set_logical_size(&self, width: i32, height: i32) {
+                SDL_RenderSetLogicalSize(self.renderer, width, height);
+            }
         }
 
         
@@ -0,0 +1,101 @@
+use fermium::{prelude::{SDL_Event, SDL_KEYDOWN, SDL_KEYUP}, events::{SDL_QUIT, SDL_CONTROLLERDEVICEADDED, SDL_CONTROLLERDEVICEREMOVED, SDL_CONTROLLERBUTTONDOWN, SDL_CONTROLLERBUTTONUP, SDL_CONTROLLERAXISMOTION, SDL_MOUSEMOTION}};
+
+// something the player did, as seen by whoever is drawing the game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputEvent {
+    KEYDOWN(i32),
+    KEYUP(i32),
+    // the window was closed
+    QUIT,
+    // a game controller was plugged in; SDL reports its device index, the environment its instance id
+    PADADDED(i32),
+    // the remaining pad events name the pad by instance id
+    PADREMOVED(i32),
+    PADBUTTONDOWN(i32, u8),
+    PADBUTTONUP(i32, u8),
+    // pad, axis, and its position from -32768 to 32767
+    PADAXIS(i32, u8, i16),
+    // where the mouse pointer moved to, in play area coordinates
+    MOUSEMOTION(i32, i32)
+}
+
+// turns a raw SDL event into an input event, ignoring key repeats and anything that isn't input
+pub fn translate(event: &SDL_Event) -> Option<InputEvent> {
+    unsafe {
+        match event.type_ {
+            SDL_KEYDOWN if event.key.repeat == 0 => Some(InputEvent::KEYDOWN(event.key.keysym.sym.0)),
+            SDL_KEYUP => Some(InputEvent::KEYUP(event.key.keysym.sym.0)),
+            SDL_QUIT => Some(InputEvent::QUIT),
+            SDL_CONTROLLERDEVICEADDED => Some(InputEvent::PADADDED(event.cdevice.which)),
+            SDL_CONTROLLERDEVICEREMOVED => Some(InputEvent::PADREMOVED(event.cdevice.which)),
+            SDL_CONTROLLERBUTTONDOWN => Some(InputEvent::PADBUTTONDOWN(event.cbutton.which.0, event.cbutton.button)),
+            SDL_CONTROLLERBUTTONUP => Some(InputEvent::PADBUTTONUP(event.cbutton.which.0, event.cbutton.button)),
+            SDL_CONTROLLERAXISMOTION => Some(InputEvent::PADAXIS(event.caxis.which.0, event.caxis.axis, event.caxis.value)),
+            SDL_MOUSEMOTION => Some(InputEvent::MOUSEMOTION(event.motion.x, event.motion.y)),
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fermium::{prelude::{SDL_Event, SDL_KEYDOWN, SDL_KEYUP}, keycode::SDL_Keycode, joystick::SDL_JoystickID, events::{SDL_EventType, SDL_CONTROLLERDEVICEADDED, SDL_CONTROLLERBUTTONDOWN, SDL_CONTROLLERAXISMOTION, SDL_MOUSEMOTION, SDL_MOUSEWHEEL}};
+
+    use super::{translate, InputEvent};
+
+    fn key_event(type_: SDL_EventType, sym: i32, repeat: u8) -> SDL_Event {
+        let mut event = SDL_Event::default();
+        event.key.type_ = type_;
+        event.key.keysym.sym = SDL_Keycode(sym);
+        event.key.repeat = repeat;
+        event
+    }
+
+    #[test]
+    fn translates_key_presses_and_releases() {
+        assert_eq!(translate(&key_event(SDL_KEYDOWN, 119, 0)), Some(InputEvent::KEYDOWN(119)));
+        assert_eq!(translate(&key_event(SDL_KEYUP, 119, 0)), Some(InputEvent::KEYUP(119)));
+    }
+
+    #[test]
+    fn ignores_key_repeats() {
+        assert_eq!(translate(&key_event(SDL_KEYDOWN, 119, 1)), None);
+    }
+
+    #[test]
+    fn translates_game_controller_events() {
+        let mut added = SDL_Event::default();
+        added.cdevice.type_ = SDL_CONTROLLERDEVICEADDED;
+        added.cdevice.which = 2;
+        assert_eq!(translate(&added), Some(InputEvent::PADADDED(2)));
+
+        let mut button = SDL_Event::default();
+        button.cbutton.type_ = SDL_CONTROLLERBUTTONDOWN;
+        button.cbutton.which = SDL_JoystickID(7);
+        button.cbutton.button = 6;
+        assert_eq!(translate(&button), Some(InputEvent::PADBUTTONDOWN(7, 6)));
+
+        let mut axis = SDL_Event::default();
+        axis.caxis.type_ = SDL_CONTROLLERAXISMOTION;
+        axis.caxis.which = SDL_JoystickID(7);
+        axis.caxis.axis = 1;
+        axis.caxis.value = -20_000;
+        assert_eq!(translate(&axis), Some(InputEvent::PADAXIS(7, 1, -20_000)));
+    }
+
+    #[test]
+    fn translates_mouse_motion() {
+        let mut motion = SDL_Event::default();
+        motion.motion.type_ = SDL_MOUSEMOTION;
+        motion.motion.x = 640;
+        motion.motion.y = 120;
+        assert_eq!(translate(&motion), Some(InputEvent::MOUSEMOTION(640, 120)));
+    }
+
+    #[test]
+    fn ignores_other_events() {
+        let mut wheel = SDL_Event::default();
+        wheel.wheel.type_ = SDL_MOUSEWHEEL;
+        assert_eq!(translate(&wheel), None);
+    }
+}